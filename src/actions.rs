@@ -0,0 +1,474 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
+
+/// Everything the user can trigger, whether from a key or the command palette.
+/// Named in snake_case (`next_panel`, `read_item`) in the config's `[keys]` table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Quit,
+    Refresh,
+    NextPanel,
+    Close,
+    OpenHomepage,
+    LoadMore,
+    ToggleScoreSort,
+    Digest,
+    ConnectTelegram,
+    ToggleTelegram,
+    Search,
+    CommandPalette,
+    ScrollUp,
+    ScrollDown,
+    ReadItem,
+    CycleTheme,
+    Zoom,
+    TogglePause,
+    CopyLink,
+    SaveItem,
+    RefreshFocused,
+    MarkAllRead,
+    CycleCategory,
+    Timeline,
+    ToggleBookmark,
+    Bookmarks,
+    MoreItems,
+    FewerItems,
+    Help,
+    ToggleLog,
+    RetryFailed,
+}
+
+impl Action {
+    pub const ALL: [Action; 31] = [
+        Action::Quit,
+        Action::Refresh,
+        Action::NextPanel,
+        Action::Close,
+        Action::OpenHomepage,
+        Action::LoadMore,
+        Action::ToggleScoreSort,
+        Action::Digest,
+        Action::ConnectTelegram,
+        Action::ToggleTelegram,
+        Action::Search,
+        Action::CommandPalette,
+        Action::ScrollUp,
+        Action::ScrollDown,
+        Action::ReadItem,
+        Action::CycleTheme,
+        Action::Zoom,
+        Action::TogglePause,
+        Action::CopyLink,
+        Action::SaveItem,
+        Action::RefreshFocused,
+        Action::MarkAllRead,
+        Action::CycleCategory,
+        Action::Timeline,
+        Action::ToggleBookmark,
+        Action::Bookmarks,
+        Action::MoreItems,
+        Action::FewerItems,
+        Action::Help,
+        Action::ToggleLog,
+        Action::RetryFailed,
+    ];
+
+    /// The action's key in the config's `[keys]` table, e.g. "next_panel".
+    pub fn config_name(self) -> String {
+        let mut name = String::new();
+        for c in format!("{:?}", self).chars() {
+            if c.is_uppercase() && !name.is_empty() {
+                name.push('_');
+            }
+            name.push(c.to_ascii_lowercase());
+        }
+        name
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::Refresh => "Refresh all feeds",
+            Action::NextPanel => "Focus next panel",
+            Action::Close => "Close overlay / clear focus",
+            Action::OpenHomepage => "Open feed homepage",
+            Action::LoadMore => "Load older items",
+            Action::ToggleScoreSort => "Toggle sort by score",
+            Action::Digest => "Show headline digest",
+            Action::ConnectTelegram => "Connect to Telegram",
+            Action::ToggleTelegram => "Show/hide Telegram column",
+            Action::Search => "Search",
+            Action::CommandPalette => "Command palette",
+            Action::ScrollUp => "Scroll focused feed up",
+            Action::ScrollDown => "Scroll focused feed down",
+            Action::ReadItem => "Read top item of focused feed",
+            Action::CycleTheme => "Switch colour theme",
+            Action::Zoom => "Full-screen focused feed",
+            Action::TogglePause => "Pause/resume headline rotation",
+            Action::CopyLink => "Copy item link to clipboard",
+            Action::SaveItem => "Save item as Markdown",
+            Action::RefreshFocused => "Refresh focused feed",
+            Action::MarkAllRead => "Mark shown items read",
+            Action::CycleCategory => "Filter by next category",
+            Action::Timeline => "Merged timeline of all feeds",
+            Action::ToggleBookmark => "Star/unstar item",
+            Action::Bookmarks => "Starred items",
+            Action::MoreItems => "More items per block",
+            Action::FewerItems => "Fewer items per block",
+            Action::Help => "Key bindings",
+            Action::ToggleLog => "Show/hide log panel",
+            Action::RetryFailed => "Retry failed or empty feeds",
+        }
+    }
+}
+
+/// Bindings used for every action the config's `[keys]` table doesn't mention.
+pub const DEFAULT_BINDINGS: &[(KeyBinding, Action)] = &[
+    (key(KeyCode::Char('q')), Action::Quit),
+    (key(KeyCode::Char('r')), Action::Refresh),
+    (key(KeyCode::Tab), Action::NextPanel),
+    (key(KeyCode::Esc), Action::Close),
+    (key(KeyCode::Char('L')), Action::OpenHomepage),
+    (key(KeyCode::Char('M')), Action::LoadMore),
+    (key(KeyCode::Char('s')), Action::ToggleScoreSort),
+    (key(KeyCode::Char('D')), Action::Digest),
+    (key(KeyCode::Char('T')), Action::ConnectTelegram),
+    (key(KeyCode::Char('t')), Action::ToggleTelegram),
+    (key(KeyCode::Char('/')), Action::Search),
+    (key(KeyCode::Char(':')), Action::CommandPalette),
+    (ctrl('p'), Action::CommandPalette),
+    (key(KeyCode::Up), Action::ScrollUp),
+    (key(KeyCode::Char('k')), Action::ScrollUp),
+    (key(KeyCode::Down), Action::ScrollDown),
+    (key(KeyCode::Char('j')), Action::ScrollDown),
+    (key(KeyCode::Enter), Action::ReadItem),
+    (key(KeyCode::Char('C')), Action::CycleTheme),
+    (key(KeyCode::Char('z')), Action::Zoom),
+    (key(KeyCode::Char(' ')), Action::TogglePause),
+    (key(KeyCode::Char('p')), Action::TogglePause),
+    (key(KeyCode::Char('y')), Action::CopyLink),
+    (key(KeyCode::Char('e')), Action::SaveItem),
+    (key(KeyCode::Char('R')), Action::RefreshFocused),
+    (key(KeyCode::Char('F')), Action::RetryFailed),
+    (key(KeyCode::Char('A')), Action::MarkAllRead),
+    (key(KeyCode::Char('c')), Action::CycleCategory),
+    (key(KeyCode::Char('a')), Action::Timeline),
+    (key(KeyCode::Char('b')), Action::ToggleBookmark),
+    (key(KeyCode::Char('B')), Action::Bookmarks),
+    (key(KeyCode::Char('+')), Action::MoreItems),
+    (key(KeyCode::Char('=')), Action::MoreItems),
+    (key(KeyCode::Char('-')), Action::FewerItems),
+    (key(KeyCode::Char('?')), Action::Help),
+    (key(KeyCode::Char('l')), Action::ToggleLog),
+];
+
+const fn key(code: KeyCode) -> KeyBinding {
+    KeyBinding { code, modifiers: KeyModifiers::NONE }
+}
+
+const fn ctrl(c: char) -> KeyBinding {
+    KeyBinding { code: KeyCode::Char(c), modifiers: KeyModifiers::CONTROL }
+}
+
+/// Keys the main loop handles itself rather than through the keymap, for the help overlay.
+pub const FIXED_KEYS: &[(&str, &str)] = &[("1-9", "Full-screen feed by number")];
+
+/// A key with the modifiers held for it. Shift isn't recorded for characters, since it already
+/// shows in the character itself ('B' rather than 'b'), nor for BackTab, which is Shift+Tab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub fn from_event(event: KeyEvent) -> Self {
+        let mut modifiers = event.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        if matches!(event.code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Self { code: event.code, modifiers }
+    }
+
+    /// "q", "B", "Tab", "ctrl+p", "alt+Enter", "F5". Modifier and key names ignore case;
+    /// single characters don't. "shift+Tab" and "BackTab" are the same key, since terminals
+    /// report Shift+Tab as BackTab.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = spec;
+        while let Some((modifier, key)) = rest.split_once('+').filter(|(_, key)| !key.is_empty()) {
+            match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => modifiers |= KeyModifiers::CONTROL,
+                "alt" => modifiers |= KeyModifiers::ALT,
+                "shift" => modifiers |= KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier \"{}\" in key \"{}\"", modifier, spec)),
+            }
+            rest = key;
+        }
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(if modifiers.contains(KeyModifiers::CONTROL) { c.to_ascii_lowercase() } else { c }),
+            _ => match rest.to_lowercase().as_str() {
+                "space" => KeyCode::Char(' '),
+                "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "esc" | "escape" => KeyCode::Esc,
+                "enter" | "return" => KeyCode::Enter,
+                "backspace" => KeyCode::Backspace,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key \"{}\"", spec)),
+                },
+            },
+        };
+        if matches!(code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Ok(Self { code, modifiers })
+    }
+}
+
+/// One or more keys for an action in `[keys]`: `quit = "q"` or `scroll_down = ["j", "Down"]`.
+#[derive(Debug, Clone)]
+pub struct KeyList(pub Vec<KeyBinding>);
+
+impl<'de> Deserialize<'de> for KeyList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            One(String),
+            Many(Vec<String>),
+        }
+        let specs = match Raw::deserialize(deserializer)? {
+            Raw::One(spec) => vec![spec],
+            Raw::Many(specs) => specs,
+        };
+        specs
+            .iter()
+            .map(|spec| KeyBinding::parse(spec.trim()))
+            .collect::<Result<_, _>>()
+            .map(KeyList)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Which action each key triggers: `DEFAULT_BINDINGS` with the config's `[keys]` applied.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    // In binding order, so the first key listed for an action is the one shown in hints
+    bindings: Vec<(KeyBinding, Action)>,
+    lookup: HashMap<KeyBinding, Action>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new(&HashMap::new()).expect("default bindings don't conflict")
+    }
+}
+
+impl Keymap {
+    /// An action named in `overrides` gets exactly the keys listed there (none for an empty list);
+    /// the rest keep their defaults. Fails with every key left bound to two actions.
+    pub fn new(overrides: &HashMap<Action, KeyList>) -> Result<Self, String> {
+        let mut bindings: Vec<(KeyBinding, Action)> =
+            DEFAULT_BINDINGS.iter().filter(|(_, action)| !overrides.contains_key(action)).copied().collect();
+        // Config order is lost in the map, so follow the palette's order instead
+        for action in Action::ALL {
+            if let Some(KeyList(keys)) = overrides.get(&action) {
+                bindings.extend(keys.iter().map(|&key| (key, action)));
+            }
+        }
+
+        let mut lookup = HashMap::new();
+        let mut conflicts = Vec::new();
+        for &(key, action) in &bindings {
+            match lookup.insert(key, action) {
+                Some(other) if other != action => {
+                    conflicts.push(format!("{} is bound to both {} and {}", key_label(key), other.config_name(), action.config_name()))
+                }
+                _ => {}
+            }
+        }
+        if !conflicts.is_empty() {
+            return Err(conflicts.join("; "));
+        }
+        Ok(Self { bindings, lookup })
+    }
+
+    pub fn action_for(&self, event: KeyEvent) -> Option<Action> {
+        self.lookup.get(&KeyBinding::from_event(event)).copied()
+    }
+
+    /// The key shown for `action` in hints and the palette.
+    pub fn key_for(&self, action: Action) -> Option<KeyBinding> {
+        self.bindings.iter().find(|(_, a)| *a == action).map(|(key, _)| *key)
+    }
+
+    /// Every key bound to `action`, in binding order.
+    pub fn keys_for(&self, action: Action) -> Vec<KeyBinding> {
+        self.bindings.iter().filter(|(_, a)| *a == action).map(|(key, _)| *key).collect()
+    }
+}
+
+/// Subsequence match, case-insensitive. Higher is better; consecutive and early hits score more.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut last_hit: Option<usize> = None;
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let hit = pos + candidate[pos..].iter().position(|&c| c == q)?;
+        score += match last_hit {
+            Some(prev) if prev + 1 == hit => 5,
+            _ => 1,
+        };
+        if hit == 0 || candidate[hit - 1] == ' ' {
+            score += 3;
+        }
+        last_hit = Some(hit);
+        pos = hit + 1;
+    }
+    Some(score - candidate.len() as i32 / 10)
+}
+
+/// Actions matching `query`, best first. An empty query lists everything in declaration order.
+pub fn filter_actions(query: &str) -> Vec<Action> {
+    let mut matches: Vec<(i32, Action)> = Action::ALL
+        .iter()
+        .filter_map(|&a| fuzzy_score(query, a.name()).map(|score| (score, a)))
+        .collect();
+    if !query.trim().is_empty() {
+        matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    }
+    matches.into_iter().map(|(_, a)| a).collect()
+}
+
+pub fn key_label(key: KeyBinding) -> String {
+    let mut label = String::new();
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        label.push_str("Ctrl+");
+    }
+    if key.modifiers.contains(KeyModifiers::ALT) {
+        label.push_str("Alt+");
+    }
+    if key.modifiers.contains(KeyModifiers::SHIFT) {
+        label.push_str("Shift+");
+    }
+    let name = match key.code {
+        // Ctrl+P reads better than Ctrl+p
+        KeyCode::Char(c) if !key.modifiers.is_empty() => c.to_uppercase().to_string(),
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::BackTab => "Shift+Tab".to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        other => format!("{:?}", other),
+    };
+    label.push_str(&name);
+    label
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn parses_modifiers_names_and_function_keys() {
+        assert_eq!(KeyBinding::parse("q"), Ok(key(KeyCode::Char('q'))));
+        assert_eq!(KeyBinding::parse("B"), Ok(key(KeyCode::Char('B'))));
+        assert_eq!(KeyBinding::parse("ctrl+P"), Ok(ctrl('p')));
+        assert_eq!(
+            KeyBinding::parse("Alt+Enter"),
+            Ok(KeyBinding { code: KeyCode::Enter, modifiers: KeyModifiers::ALT })
+        );
+        assert_eq!(
+            KeyBinding::parse("ctrl+alt+x"),
+            Ok(KeyBinding { code: KeyCode::Char('x'), modifiers: KeyModifiers::CONTROL | KeyModifiers::ALT })
+        );
+        // Shift is already in the character
+        assert_eq!(KeyBinding::parse("shift+b"), Ok(key(KeyCode::Char('b'))));
+        assert_eq!(KeyBinding::parse("ctrl++"), Ok(ctrl('+')));
+        assert_eq!(KeyBinding::parse("space"), Ok(key(KeyCode::Char(' '))));
+        assert_eq!(KeyBinding::parse("PageDown"), Ok(key(KeyCode::PageDown)));
+        assert_eq!(KeyBinding::parse("F5"), Ok(key(KeyCode::F(5))));
+        assert_eq!(KeyBinding::parse("f12"), Ok(key(KeyCode::F(12))));
+    }
+
+    #[test]
+    fn shift_tab_is_backtab() {
+        assert_eq!(KeyBinding::parse("shift+Tab"), Ok(key(KeyCode::BackTab)));
+        assert_eq!(KeyBinding::parse("BackTab"), Ok(key(KeyCode::BackTab)));
+        assert_eq!(KeyBinding::parse("Tab"), Ok(key(KeyCode::Tab)));
+        assert_eq!(key_label(key(KeyCode::BackTab)), "Shift+Tab");
+
+        let mut overrides = HashMap::new();
+        overrides.insert(Action::NextPanel, KeyList(vec![KeyBinding::parse("shift+tab").unwrap()]));
+        let keymap = Keymap::new(&overrides).unwrap();
+        // Terminals differ on whether they also set SHIFT with BackTab
+        assert_eq!(keymap.action_for(event(KeyCode::BackTab, KeyModifiers::SHIFT)), Some(Action::NextPanel));
+        assert_eq!(keymap.action_for(event(KeyCode::BackTab, KeyModifiers::NONE)), Some(Action::NextPanel));
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_modifiers() {
+        assert_eq!(KeyBinding::parse("hyper+q"), Err("unknown modifier \"hyper\" in key \"hyper+q\"".to_string()));
+        assert_eq!(KeyBinding::parse("PgDn"), Err("unknown key \"PgDn\"".to_string()));
+        assert!(KeyBinding::parse("F13").is_err());
+        assert!(KeyBinding::parse("F0").is_err());
+        assert!(KeyBinding::parse("").is_err());
+    }
+
+    #[test]
+    fn events_drop_shift_only_for_characters() {
+        assert_eq!(
+            KeyBinding::from_event(event(KeyCode::Char('B'), KeyModifiers::SHIFT)),
+            key(KeyCode::Char('B'))
+        );
+        assert_eq!(
+            KeyBinding::from_event(event(KeyCode::Up, KeyModifiers::SHIFT)),
+            KeyBinding { code: KeyCode::Up, modifiers: KeyModifiers::SHIFT }
+        );
+        // Modifiers the keymap doesn't track are ignored
+        assert_eq!(
+            KeyBinding::from_event(event(KeyCode::Char('p'), KeyModifiers::CONTROL | KeyModifiers::SUPER)),
+            ctrl('p')
+        );
+    }
+
+    #[test]
+    fn keymap_reports_every_conflict() {
+        let mut overrides = HashMap::new();
+        overrides.insert(Action::Refresh, KeyList(vec![key(KeyCode::Char('q')), key(KeyCode::Tab)]));
+        let err = Keymap::new(&overrides).unwrap_err();
+        assert!(err.contains("q is bound to both quit and refresh"), "{}", err);
+        assert!(err.contains("Tab is bound to both next_panel and refresh"), "{}", err);
+
+        // Moving the default away frees its key
+        overrides.insert(Action::Quit, KeyList(vec![ctrl('c')]));
+        overrides.insert(Action::NextPanel, KeyList(vec![]));
+        let keymap = Keymap::new(&overrides).unwrap();
+        assert_eq!(keymap.action_for(event(KeyCode::Char('q'), KeyModifiers::NONE)), Some(Action::Refresh));
+        assert_eq!(keymap.key_for(Action::Refresh), Some(key(KeyCode::Char('q'))));
+        assert_eq!(keymap.key_for(Action::NextPanel), None);
+    }
+}
@@ -0,0 +1,131 @@
+use chrono::{DateTime, Utc};
+use directories::ProjectDirs;
+use rss_tui::Feed;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+const DEFAULT_CACHE_DIR: &str = "feed-cache";
+
+/// The last successfully fetched copy of each feed, shown instead of an error while offline.
+pub struct FeedCache {
+    dir: PathBuf,
+}
+
+/// One cache file; the URL is kept so a hash collision reads as a miss, not another feed.
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    url: String,
+    fetched_at: DateTime<Utc>,
+    feed: Feed,
+}
+
+impl FeedCache {
+    /// `$RSS_TUI_CACHE`, else `feed-cache/` under the per-user cache directory (`~/.cache/rss-tui/`
+    /// on Linux), created on the first save.
+    pub fn open_default() -> Self {
+        let dir = env::var("RSS_TUI_CACHE")
+            .map(PathBuf::from)
+            .ok()
+            .or_else(|| ProjectDirs::from("", "", "rss-tui").map(|dirs| dirs.cache_dir().join(DEFAULT_CACHE_DIR)))
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_CACHE_DIR));
+        Self::open(dir)
+    }
+
+    pub fn open(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn save(&self, url: &str, feed: &Feed, at: DateTime<Utc>) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let entry = Entry { url: url.to_string(), fetched_at: at, feed: feed.clone() };
+        let json = serde_json::to_vec(&entry).map_err(io::Error::other)?;
+        // Written aside and renamed, so a crash mid-write can't leave half a file behind
+        let path = self.path_for(url);
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, json)?;
+        fs::rename(tmp, path)
+    }
+
+    /// The cached copy of `url` and when it was fetched, unless it's missing or older than `max_age`.
+    pub fn load(&self, url: &str, max_age: chrono::Duration, now: DateTime<Utc>) -> Option<(Feed, DateTime<Utc>)> {
+        let entry = self.entry(url)?;
+        (now - entry.fetched_at <= max_age).then_some((entry.feed, entry.fetched_at))
+    }
+
+    /// Marks the copy of `url` as current at `at`, after the server answered 304 for it.
+    pub fn touch(&self, url: &str, at: DateTime<Utc>) -> io::Result<()> {
+        match self.entry(url) {
+            Some(entry) => self.save(url, &entry.feed, at),
+            None => Ok(()),
+        }
+    }
+
+    fn entry(&self, url: &str) -> Option<Entry> {
+        let raw = fs::read(self.path_for(url)).ok()?;
+        let entry: Entry = serde_json::from_slice(&raw).ok()?;
+        (entry.url == url).then_some(entry)
+    }
+
+    fn path_for(&self, url: &str) -> PathBuf {
+        self.dir.join(format!("{:016x}.json", fnv1a(url.as_bytes())))
+    }
+}
+
+/// FNV-1a, for file names that stay the same across builds (unlike `DefaultHasher`).
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rss_tui::FeedItem;
+
+    const URL: &str = "https://example.com/rss";
+
+    fn feed() -> Feed {
+        Feed { title: "Example".to_string(), items: vec![FeedItem { title: "One".to_string(), ..FeedItem::default() }], ..Feed::default() }
+    }
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        use chrono::TimeZone;
+        Utc.with_ymd_and_hms(2026, 3, 2, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn round_trips_until_too_old() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = FeedCache::open(dir.path().join("feed-cache"));
+        assert!(cache.load(URL, chrono::Duration::hours(1), at(9)).is_none());
+        cache.save(URL, &feed(), at(8)).unwrap();
+
+        let (loaded, fetched_at) = cache.load(URL, chrono::Duration::hours(1), at(9)).unwrap();
+        assert_eq!((loaded.title.as_str(), loaded.items[0].title.as_str(), fetched_at), ("Example", "One", at(8)));
+        assert!(cache.load(URL, chrono::Duration::hours(1), at(10)).is_none(), "two hours old");
+    }
+
+    #[test]
+    fn touch_keeps_an_unchanged_feed_fresh() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = FeedCache::open(dir.path().to_path_buf());
+        cache.save(URL, &feed(), at(1)).unwrap();
+        cache.touch(URL, at(9)).unwrap();
+        assert_eq!(cache.load(URL, chrono::Duration::hours(1), at(10)).map(|(_, at)| at), Some(at(9)));
+        // Nothing to touch for a feed that was never saved
+        cache.touch("https://other.example/rss", at(9)).unwrap();
+        assert!(cache.load("https://other.example/rss", chrono::Duration::hours(1), at(9)).is_none());
+    }
+
+    #[test]
+    fn another_feed_in_the_same_file_is_a_miss() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = FeedCache::open(dir.path().to_path_buf());
+        cache.save(URL, &feed(), at(8)).unwrap();
+        // As if a different URL had hashed to this file name
+        fs::rename(cache.path_for(URL), cache.path_for("https://collides.example/rss")).unwrap();
+        assert!(cache.load("https://collides.example/rss", chrono::Duration::hours(1), at(8)).is_none());
+    }
+}
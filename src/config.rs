@@ -0,0 +1,604 @@
+use crate::actions::{Action, KeyList, Keymap};
+use crate::rss_funcs::{self, ProxyChoice};
+use chrono::NaiveTime;
+use chrono_tz::Tz;
+use ratatui::style::Color;
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use toml_edit::DocumentMut;
+
+const DEFAULT_CONFIG_PATH: &str = "config.toml";
+const DEFAULT_FEEDS_PATH: &str = "feeds.toml";
+/// Bump when keys are renamed or restructured, and add a step to `MIGRATIONS`.
+pub const CONFIG_VERSION: i64 = 1;
+/// `MIGRATIONS[n]` upgrades a version-n document to version n+1.
+const MIGRATIONS: [fn(&mut DocumentMut); CONFIG_VERSION as usize] = [v0_to_v1];
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub version: i64,
+    /// Feeds in layout order (column, then slot); missing means the built-in set
+    pub feeds: Vec<FeedConfig>,
+    /// Percent of the screen for each feed column, left to right; Telegram gets the rest
+    pub column_widths: Vec<u16>,
+    /// Feed blocks stacked in each column
+    pub slots_per_column: usize,
+    pub quiet_hours: Option<QuietHours>,
+    /// Default rotation for every feed
+    pub rotation: Rotation,
+    /// Per-feed rotation overrides keyed by feed URL
+    pub feed_rotation: HashMap<String, Rotation>,
+    /// Base score for every item of a feed, keyed by feed URL
+    pub feed_priority: HashMap<String, i32>,
+    pub keyword_rules: Vec<KeywordRule>,
+    /// Items scoring at least this much are drawn prominently
+    pub highlight_score: i32,
+    /// Check Telegram is reachable before attempting the login
+    pub telegram_preflight: bool,
+    /// How many times in a row a stopped Telegram monitor (a failed re-login, or a connection that
+    /// didn't come back within its reconnect attempts) is restarted before the column gives up;
+    /// ten minutes of running normally resets the count
+    pub telegram_max_restarts: u32,
+    pub empty_policy: EmptyPolicy,
+    /// Briefly focus a feed that just received new items
+    pub auto_focus_new: bool,
+    pub auto_focus_secs: u64,
+    /// Draw item images in the reader on terminals with sixel, kitty or iTerm2 graphics.
+    /// Off by default: turning it on queries the terminal at startup, and a terminal that never
+    /// answers leaves the query reading keypresses meant for the app.
+    pub inline_images: bool,
+    /// Where [e] saves items as Markdown; created on first save
+    pub export_dir: PathBuf,
+    /// Desktop notifications for new items in feeds marked `notify = true`; off by default
+    pub desktop_notifications: bool,
+    /// Append every received Telegram message to this JSONL file
+    pub telegram_log: Option<PathBuf>,
+    /// Refresh when the terminal regains focus and pause rotation while it's in the background.
+    /// Needs a terminal that reports focus changes.
+    pub refresh_on_focus: bool,
+    /// Click to focus and open, wheel to scroll; off leaves the mouse to the terminal (e.g. for selecting text)
+    pub mouse: bool,
+    pub footer_overflow: FooterOverflow,
+    /// Proxy URL for every feed (http, https or socks5), instead of HTTP_PROXY/HTTPS_PROXY/ALL_PROXY
+    pub proxy: Option<String>,
+    /// Per-feed proxy URL keyed by feed URL, taking precedence over `proxy`
+    pub feed_proxy: HashMap<String, String>,
+    /// Feeds that must bypass any proxy
+    pub no_proxy_feeds: Vec<String>,
+    pub error_display: ErrorDisplay,
+    /// Stacked Telegram panels; none configured means a single panel with every chat
+    pub telegram_panels: Vec<TelegramPanel>,
+    /// Cap on headline width in terminal columns, even when the block is wide enough for more
+    pub max_title_chars: Option<usize>,
+    pub title_overflow: TitleOverflow,
+    /// IANA zone for item dates (e.g. "Asia/Singapore"); unset means local time
+    #[serde(deserialize_with = "de_timezone")]
+    pub timezone: Option<Tz>,
+    /// Seconds between automatic refreshes of every feed; `--interval` overrides it
+    pub refresh_interval_secs: u64,
+    /// Seconds between rotation steps of the feed blocks
+    pub rotate_interval_secs: u64,
+    pub connect_timeout_secs: u64,
+    /// Total time allowed for one feed request
+    pub fetch_timeout_secs: u64,
+    /// Read marks older than this many days are forgotten at startup
+    pub read_retention_days: u64,
+    /// While offline, feeds fall back to their last good copy if it's at most this many hours old;
+    /// 0 turns the cache off
+    pub offline_cache_hours: u64,
+    /// Sent with every feed request; `$RSS_TUI_USER_AGENT` takes precedence
+    pub user_agent: String,
+    /// Built-in theme name ("matrix", "light", "high-contrast") or path to a theme TOML file;
+    /// `--theme` overrides it
+    pub theme: String,
+    /// Keys per action, replacing that action's defaults; see `keymap` for the result
+    pub keys: HashMap<Action, KeyList>,
+    /// Built from `keys` at load
+    #[serde(skip)]
+    pub keymap: Keymap,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            feeds: default_feeds(),
+            column_widths: vec![40, 40],
+            slots_per_column: 3,
+            quiet_hours: None,
+            rotation: Rotation::default(),
+            feed_rotation: HashMap::new(),
+            feed_priority: HashMap::new(),
+            keyword_rules: Vec::new(),
+            highlight_score: 5,
+            telegram_preflight: true,
+            telegram_max_restarts: 5,
+            empty_policy: EmptyPolicy::default(),
+            auto_focus_new: false,
+            auto_focus_secs: 10,
+            inline_images: false,
+            export_dir: PathBuf::from("articles"),
+            desktop_notifications: false,
+            telegram_log: None,
+            refresh_on_focus: false,
+            mouse: true,
+            footer_overflow: FooterOverflow::default(),
+            proxy: None,
+            feed_proxy: HashMap::new(),
+            no_proxy_feeds: Vec::new(),
+            error_display: ErrorDisplay::default(),
+            telegram_panels: Vec::new(),
+            max_title_chars: None,
+            title_overflow: TitleOverflow::default(),
+            timezone: None,
+            refresh_interval_secs: 300,
+            rotate_interval_secs: 15,
+            connect_timeout_secs: rss_funcs::DEFAULT_CONNECT_TIMEOUT.as_secs(),
+            fetch_timeout_secs: rss_funcs::DEFAULT_TIMEOUT.as_secs(),
+            user_agent: rss_funcs::DEFAULT_USER_AGENT.to_string(),
+            read_retention_days: 90,
+            offline_cache_hours: 72,
+            theme: "matrix".to_string(),
+            keys: HashMap::new(),
+            keymap: Keymap::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeedConfig {
+    pub url: String,
+    pub title: String,
+    /// Named ("cyan") or hex ("#ff00ff") colour; defaults to the theme's `feed_colors`, in turn
+    #[serde(default, deserialize_with = "de_color")]
+    pub color: Option<Color>,
+    /// Short tag drawn next to each headline
+    #[serde(default)]
+    pub category: Option<String>,
+    pub column: usize,
+    pub slot: usize,
+    /// Pop a desktop notification for each new item (needs `desktop_notifications`)
+    #[serde(default)]
+    pub notify: bool,
+}
+
+/// `feeds.toml`: nothing but the `[[feeds]]` list.
+#[derive(Debug, Deserialize)]
+struct FeedsFile {
+    #[serde(default)]
+    feeds: Vec<FeedConfig>,
+}
+
+impl FeedConfig {
+    fn builtin(url: &str, title: &str, color: Color, category: Option<&str>, column: usize, slot: usize) -> Self {
+        Self {
+            url: url.to_string(),
+            title: title.to_string(),
+            color: Some(color),
+            category: category.map(str::to_string),
+            column,
+            slot,
+            notify: false,
+        }
+    }
+}
+
+fn default_feeds() -> Vec<FeedConfig> {
+    vec![
+        // Left column (tech)
+        FeedConfig::builtin("https://feeds.feedburner.com/TheHackersNews", " THE HACKER NEWS ", crate::MATRIX_GREEN, None, 0, 0),
+        FeedConfig::builtin("https://www.computerweekly.com/rss/Latest-IT-news.xml", " COMPUTER WEEKLY ", crate::MATRIX_GREEN, None, 0, 1),
+        FeedConfig::builtin("https://sdtimes.com/feed/", " SOFTWARE DEV TIMES ", crate::MATRIX_GREEN, None, 0, 2),
+        // Middle column (news)
+        FeedConfig::builtin("https://www.investing.com/rss/news_25.rss", " STOCKS ", crate::WORLD_MAGENTA, Some("Stocks"), 1, 0),
+        FeedConfig::builtin(
+            "https://www.channelnewsasia.com/api/v1/rss-outbound-feed?_format=xml",
+            " WORLD NEWS", crate::SPORTS_CYAN, Some("World"), 1, 1,
+        ),
+        FeedConfig::builtin(
+            "https://www.channelnewsasia.com/api/v1/rss-outbound-feed?_format=xml&category=10416",
+            " LOCAL NEWS ", crate::NEWS_GOLD, Some("Singapore"), 1, 2,
+        ),
+    ]
+}
+
+/// Fills the grid column by column in list order, dropping feeds that don't fit; returns how many were dropped.
+pub fn place_in_grid(feeds: &mut Vec<FeedConfig>, columns: usize, slots: usize) -> usize {
+    let dropped = feeds.len().saturating_sub(columns * slots);
+    feeds.truncate(columns * slots);
+    for (i, feed) in feeds.iter_mut().enumerate() {
+        feed.column = i / slots;
+        feed.slot = i % slots;
+    }
+    dropped
+}
+
+/// Adds `bonus` to any item whose title or description mentions `keyword` (case-insensitive).
+#[derive(Debug, Clone, Deserialize)]
+pub struct KeywordRule {
+    pub keyword: String,
+    pub bonus: i32,
+}
+
+/// How a feed block advances on each tick.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Rotation {
+    /// Wrap back to the newest item after the oldest (original behaviour)
+    #[default]
+    Loop,
+    /// Advance until the oldest item is visible, then hold
+    Once,
+    /// Never advance
+    Off,
+}
+
+/// What a refresh that comes back with no items, or under half as many as the last one, does to a feed block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmptyPolicy {
+    /// Always take the new result, even if it blanks the block
+    Replace,
+    /// Keep the last good items and mark them stale. A second shrunken refresh in a row is accepted,
+    /// since the feed has most likely just got shorter
+    #[default]
+    KeepOnEmpty,
+}
+
+/// What the footer does when it is wider than the terminal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FooterOverflow {
+    /// Stay on one row, dropping the least important segments first
+    #[default]
+    Truncate,
+    /// Spill onto a second row before dropping anything
+    Wrap,
+}
+
+/// What a feed block does with a headline wider than the space before its date.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TitleOverflow {
+    /// Cut it short with "..."
+    #[default]
+    Truncate,
+    /// Carry the rest onto a second, indented row while the block has rows to spare
+    Wrap,
+}
+
+/// Where a failed fetch is reported in its feed block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorDisplay {
+    /// A terse "(err)" in the block title
+    Compact,
+    /// The full message in the block body
+    Verbose,
+    #[default]
+    Both,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TelegramPanel {
+    pub title: String,
+    /// Chats routed to this panel; empty shows every monitored chat
+    #[serde(default)]
+    pub chat_ids: Vec<i64>,
+}
+
+impl TelegramPanel {
+    pub fn accepts(&self, chat_id: i64) -> bool {
+        self.chat_ids.is_empty() || self.chat_ids.contains(&chat_id)
+    }
+}
+
+/// Local time window (e.g. 23:00 -> 07:00) in which notifications and animations are held back.
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct QuietHours {
+    #[serde(deserialize_with = "de_time")]
+    pub start: NaiveTime,
+    #[serde(deserialize_with = "de_time")]
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    pub fn contains(&self, now: NaiveTime) -> bool {
+        if self.start <= self.end {
+            now >= self.start && now < self.end
+        } else {
+            // Window wraps past midnight
+            now >= self.start || now < self.end
+        }
+    }
+}
+
+impl Config {
+    /// Reads `config.toml` (or `$RSS_TUI_CONFIG`), and the feeds from `feeds.toml` (or `$RSS_TUI_FEEDS`).
+    /// A missing file means defaults; without `feeds.toml`, `[[feeds]]` in `config.toml` is used.
+    /// Older schema versions are upgraded in place, keeping a backup of the original.
+    pub fn load() -> anyhow::Result<Self> {
+        Self::load_from(&config_path(), &feeds_path())
+    }
+
+    fn load_from(path: &Path, feeds_path: &Path) -> anyhow::Result<Self> {
+        let (mut config, mut has_feeds) = if path.exists() { Self::read(path)? } else { (Self::default(), false) };
+        let mut feeds_source = path;
+        if feeds_path.exists() {
+            if has_feeds {
+                eprintln!("warning: using the feeds in {}; [[feeds]] in {} is ignored", feeds_path.display(), path.display());
+            }
+            let raw = fs::read_to_string(feeds_path)?;
+            let file: FeedsFile =
+                toml::from_str(&raw).map_err(|e| anyhow::anyhow!("Invalid feeds {}: {}", feeds_path.display(), e))?;
+            config.feeds = file.feeds;
+            has_feeds = true;
+            feeds_source = feeds_path;
+        }
+        // The built-in feeds follow whatever grid was configured
+        if !has_feeds {
+            let (columns, slots) = (config.feed_columns(), config.slots_per_column);
+            let dropped = place_in_grid(&mut config.feeds, columns, slots);
+            if dropped > 0 {
+                eprintln!("warning: the layout has room for {} of the built-in feeds", config.feeds.len());
+            }
+        }
+        config.validate_feeds().map_err(|e| anyhow::anyhow!("Invalid config {}: {}", feeds_source.display(), e))?;
+        Ok(config)
+    }
+
+    /// The settings from `config.toml`, and whether it listed `[[feeds]]` of its own.
+    fn read(path: &Path) -> anyhow::Result<(Self, bool)> {
+        let raw = fs::read_to_string(path)?;
+        let mut doc: DocumentMut = raw
+            .parse()
+            .map_err(|e| anyhow::anyhow!("Invalid config {}: {}", path.display(), e))?;
+
+        // Configs written before the version key existed count as version 0
+        let version = doc.get("version").and_then(|v| v.as_integer()).unwrap_or(0);
+        if version > CONFIG_VERSION {
+            eprintln!(
+                "warning: {} is config version {} but this build understands up to {}; unknown settings are ignored",
+                path.display(), version, CONFIG_VERSION
+            );
+        } else if version < CONFIG_VERSION {
+            migrate(path, &mut doc, version)?;
+        }
+
+        let mut config: Config =
+            toml::from_str(&doc.to_string()).map_err(|e| anyhow::anyhow!("Invalid config {}: {}", path.display(), e))?;
+        config.validate_layout().map_err(|e| anyhow::anyhow!("Invalid config {}: {}", path.display(), e))?;
+        config.keymap = Keymap::new(&config.keys).map_err(|e| anyhow::anyhow!("Invalid config {}: {}", path.display(), e))?;
+        Ok((config, doc.contains_key("feeds")))
+    }
+
+    pub fn feed_columns(&self) -> usize {
+        self.column_widths.len()
+    }
+
+    /// Percent left over for the Telegram column.
+    pub fn telegram_width(&self) -> u16 {
+        100 - self.column_widths.iter().sum::<u16>()
+    }
+
+    fn validate_layout(&self) -> anyhow::Result<()> {
+        if self.column_widths.is_empty() || self.column_widths.contains(&0) {
+            anyhow::bail!("column_widths needs at least one column, each wider than 0%");
+        }
+        let total: u16 = self.column_widths.iter().sum();
+        if total >= 100 {
+            anyhow::bail!(
+                "column_widths add up to {}%; keep them under 100 to leave room for Telegram (hide it with t or --no-telegram)",
+                total
+            );
+        }
+        if self.slots_per_column == 0 {
+            anyhow::bail!("slots_per_column must be at least 1");
+        }
+        Ok(())
+    }
+
+    /// Rejects feeds placed outside the grid or on top of each other, then sorts them into layout order.
+    fn validate_feeds(&mut self) -> anyhow::Result<()> {
+        for (i, feed) in self.feeds.iter().enumerate() {
+            if feed.column >= self.feed_columns() || feed.slot >= self.slots_per_column {
+                anyhow::bail!(
+                    "feed \"{}\" is at column {} slot {}; columns go up to {} and slots up to {}",
+                    feed.url, feed.column, feed.slot, self.feed_columns() - 1, self.slots_per_column - 1
+                );
+            }
+            if let Some(other) = self.feeds[..i].iter().find(|f| (f.column, f.slot) == (feed.column, feed.slot)) {
+                anyhow::bail!(
+                    "feeds \"{}\" and \"{}\" both use column {} slot {}",
+                    other.url, feed.url, feed.column, feed.slot
+                );
+            }
+        }
+        self.feeds.sort_by_key(|f| (f.column, f.slot));
+        Ok(())
+    }
+
+    pub fn refresh_interval(&self) -> Duration {
+        Duration::from_secs(self.refresh_interval_secs.max(1))
+    }
+
+    pub fn rotate_interval(&self) -> Duration {
+        Duration::from_secs(self.rotate_interval_secs.max(1))
+    }
+
+    pub fn user_agent(&self) -> String {
+        env::var("RSS_TUI_USER_AGENT").unwrap_or_else(|_| self.user_agent.clone())
+    }
+
+    pub fn rotation_for(&self, url: &str) -> Rotation {
+        self.feed_rotation.get(url).copied().unwrap_or(self.rotation)
+    }
+
+    pub fn telegram_panels(&self) -> Vec<TelegramPanel> {
+        if self.telegram_panels.is_empty() {
+            vec![TelegramPanel { title: "TELEGRAM".to_string(), chat_ids: Vec::new() }]
+        } else {
+            self.telegram_panels.clone()
+        }
+    }
+
+    /// `no_proxy_feeds`, then `feed_proxy`, then `proxy`, then whatever the environment says.
+    pub fn proxy_for(&self, url: &str) -> ProxyChoice {
+        if self.no_proxy_feeds.iter().any(|u| u == url) {
+            ProxyChoice::Direct
+        } else if let Some(proxy) = self.feed_proxy.get(url).or(self.proxy.as_ref()) {
+            ProxyChoice::Via(proxy.clone())
+        } else {
+            ProxyChoice::System
+        }
+    }
+
+    /// Feed base priority plus every matching keyword bonus.
+    pub fn score(&self, url: &str, title: &str, description: &str) -> i32 {
+        let title = title.to_lowercase();
+        let description = description.to_lowercase();
+        let bonus: i32 = self
+            .keyword_rules
+            .iter()
+            .filter(|rule| {
+                let keyword = rule.keyword.to_lowercase();
+                title.contains(&keyword) || description.contains(&keyword)
+            })
+            .map(|rule| rule.bonus)
+            .sum();
+        self.feed_priority.get(url).copied().unwrap_or(0) + bonus
+    }
+}
+
+fn migrate(path: &Path, doc: &mut DocumentMut, from: i64) -> anyhow::Result<()> {
+    let backup = path.with_extension(format!("toml.v{}.bak", from));
+    fs::copy(path, &backup)?;
+    for step in &MIGRATIONS[from.max(0) as usize..] {
+        step(doc);
+    }
+    doc["version"] = toml_edit::value(CONFIG_VERSION);
+    fs::write(path, doc.to_string())?;
+    eprintln!(
+        "Upgraded {} from config version {} to {} (original saved as {})",
+        path.display(), from, CONFIG_VERSION, backup.display()
+    );
+    Ok(())
+}
+
+/// Version-less configs predate the `version` key; no settings changed shape.
+fn v0_to_v1(_doc: &mut DocumentMut) {}
+
+/// `--config <path>`, then `$RSS_TUI_CONFIG`, then `config.toml`.
+pub fn config_path() -> PathBuf {
+    arg_value("--config")
+        .or_else(|| env::var("RSS_TUI_CONFIG").ok())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_PATH))
+}
+
+/// `--feeds <path>`, then `$RSS_TUI_FEEDS`, then `feeds.toml`.
+pub fn feeds_path() -> PathBuf {
+    arg_value("--feeds")
+        .or_else(|| env::var("RSS_TUI_FEEDS").ok())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_FEEDS_PATH))
+}
+
+/// A leading word like `check` that picks another mode instead of the TUI.
+pub fn subcommand() -> Option<String> {
+    env::args().nth(1).filter(|arg| !arg.starts_with('-'))
+}
+
+/// Whether `flag` appears on the command line.
+pub fn has_flag(flag: &str) -> bool {
+    env::args().any(|arg| arg == flag)
+}
+
+/// Value following `flag` on the command line, if given.
+pub fn arg_value(flag: &str) -> Option<String> {
+    env::args().skip_while(|arg| arg != flag).nth(1)
+}
+
+fn de_color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Color>, D::Error> {
+    crate::theme::de_color(deserializer).map(Some)
+}
+
+fn de_timezone<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Tz>, D::Error> {
+    let raw = String::deserialize(deserializer)?;
+    match raw.trim() {
+        "local" => Ok(None),
+        name => name.parse::<Tz>().map(Some).map_err(serde::de::Error::custom),
+    }
+}
+
+fn de_time<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveTime, D::Error> {
+    let raw = String::deserialize(deserializer)?;
+    NaiveTime::parse_from_str(raw.trim(), "%H:%M").map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(url: &str, column: usize, slot: usize) -> String {
+        format!("[[feeds]]\nurl = \"{}\"\ntitle = \"T\"\ncolumn = {}\nslot = {}\n", url, column, slot)
+    }
+
+    #[test]
+    fn each_feed_gets_its_own_proxy_choice() {
+        let config = Config {
+            feed_proxy: HashMap::from([
+                ("https://internal.example/rss".to_string(), "http://proxy.example:3128".to_string()),
+                ("https://both.example/rss".to_string(), "http://proxy.example:3128".to_string()),
+            ]),
+            no_proxy_feeds: vec!["https://both.example/rss".to_string()],
+            ..Config::default()
+        };
+        assert_eq!(config.proxy_for("https://internal.example/rss"), ProxyChoice::Via("http://proxy.example:3128".to_string()));
+        assert_eq!(config.proxy_for("https://both.example/rss"), ProxyChoice::Direct, "no_proxy_feeds wins");
+        assert_eq!(config.proxy_for("https://other.example/rss"), ProxyChoice::System);
+    }
+
+    #[test]
+    fn the_global_proxy_sits_below_per_feed_settings() {
+        let config = Config {
+            proxy: Some("socks5://global.example:1080".to_string()),
+            feed_proxy: HashMap::from([("https://a.example/rss".to_string(), "http://proxy.example:3128".to_string())]),
+            no_proxy_feeds: vec!["https://b.example/rss".to_string()],
+            ..Config::default()
+        };
+        assert_eq!(config.proxy_for("https://a.example/rss"), ProxyChoice::Via("http://proxy.example:3128".to_string()));
+        assert_eq!(config.proxy_for("https://b.example/rss"), ProxyChoice::Direct);
+        assert_eq!(config.proxy_for("https://c.example/rss"), ProxyChoice::Via("socks5://global.example:1080".to_string()));
+    }
+
+    #[test]
+    fn feeds_toml_takes_over_from_config_toml() {
+        let dir = tempfile::tempdir().unwrap();
+        let (config_file, feeds_file) = (dir.path().join("config.toml"), dir.path().join("feeds.toml"));
+        fs::write(&config_file, format!("version = 1\nslots_per_column = 2\n{}", feed("https://a.example/rss", 0, 0))).unwrap();
+
+        let config = Config::load_from(&config_file, &feeds_file).unwrap();
+        assert_eq!(config.feeds.iter().map(|f| f.url.as_str()).collect::<Vec<_>>(), ["https://a.example/rss"]);
+
+        fs::write(&feeds_file, feed("https://b.example/rss", 1, 1) + &feed("https://c.example/rss", 0, 1)).unwrap();
+        let config = Config::load_from(&config_file, &feeds_file).unwrap();
+        assert_eq!(config.slots_per_column, 2, "other settings still come from config.toml");
+        let urls: Vec<_> = config.feeds.iter().map(|f| f.url.as_str()).collect();
+        assert_eq!(urls, ["https://c.example/rss", "https://b.example/rss"]);
+    }
+
+    #[test]
+    fn feeds_toml_slots_are_checked_against_the_grid() {
+        let dir = tempfile::tempdir().unwrap();
+        let feeds_file = dir.path().join("feeds.toml");
+        fs::write(&feeds_file, feed("https://a.example/rss", 0, 3)).unwrap();
+        let err = Config::load_from(&dir.path().join("config.toml"), &feeds_file).unwrap_err().to_string();
+        assert!(err.contains("feeds.toml") && err.contains("slot 3"), "{}", err);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::rss_funcs::FeedItem;
+use chrono_tz::Tz;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+// Longest slug used for a file name, in characters
+const MAX_SLUG_CHARS: usize = 60;
+
+/// Writes `item` into `dir` as Markdown, named after its title, and returns the path.
+/// A name that's already taken gets "-2", "-3", ... rather than overwriting the earlier file.
+pub fn save_markdown(item: &FeedItem, dir: &Path, tz: Option<Tz>) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let slug = slugify(&item.title);
+    for n in 1.. {
+        let path = dir.join(file_name(&slug, n));
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                file.write_all(to_markdown(item, tz).as_bytes())?;
+                return Ok(path);
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    unreachable!("ran out of file name counters")
+}
+
+/// Lowercase letters and digits, with every other run of characters turned into one '-'.
+fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug: String = slug.chars().take(MAX_SLUG_CHARS).collect();
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() { "untitled".to_string() } else { slug.to_string() }
+}
+
+/// `slug.md`, then `slug-2.md`, `slug-3.md`, ... for later attempts.
+fn file_name(slug: &str, attempt: usize) -> String {
+    if attempt <= 1 { format!("{}.md", slug) } else { format!("{}-{}.md", slug, attempt) }
+}
+
+/// Title as the heading, then the date and link, then the full article (or the summary without one).
+fn to_markdown(item: &FeedItem, tz: Option<Tz>) -> String {
+    let mut out = format!("# {}\n\n", item.title.trim());
+    if item.published.is_some() {
+        out.push_str(&format!("- Date: {}\n", item.full_date(tz)));
+    }
+    if !item.link.is_empty() {
+        out.push_str(&format!("- Source: <{}>\n", item.link));
+    }
+    if !item.body().is_empty() {
+        out.push_str(&format!("\n{}\n", item.body().trim()));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn article() -> FeedItem {
+        FeedItem {
+            title: "  Rust 2026: What's New?  ".to_string(),
+            published: "2026-03-02T12:00:00Z".parse().ok(),
+            summary: "Teaser".to_string(),
+            full_content: Some("Full text.".to_string()),
+            link: "https://example.com/rust".to_string(),
+            ..FeedItem::default()
+        }
+    }
+
+    #[test]
+    fn slugs_are_lowercase_words_joined_by_dashes() {
+        assert_eq!(slugify("  Rust 2026: What's New?  "), "rust-2026-what-s-new");
+        assert_eq!(slugify("Café déjà vu"), "café-déjà-vu");
+        assert_eq!(slugify("?!"), "untitled");
+        assert_eq!(slugify(&"word ".repeat(30)).chars().count(), MAX_SLUG_CHARS - 1, "cut, then the dangling '-' dropped");
+    }
+
+    #[test]
+    fn markdown_has_heading_date_link_and_body() {
+        let markdown = to_markdown(&article(), Some(chrono_tz::UTC));
+        assert_eq!(
+            markdown,
+            "# Rust 2026: What's New?\n\n- Date: Mon 02 Mar 2026 12:00\n- Source: <https://example.com/rust>\n\nFull text.\n"
+        );
+        let bare = FeedItem { title: "Bare".to_string(), ..FeedItem::default() };
+        assert_eq!(to_markdown(&bare, None), "# Bare\n\n");
+    }
+
+    #[test]
+    fn saving_twice_keeps_both_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = save_markdown(&article(), dir.path(), None).unwrap();
+        let second = save_markdown(&article(), dir.path(), None).unwrap();
+        assert_eq!(first.file_name().unwrap(), "rust-2026-what-s-new.md");
+        assert_eq!(second.file_name().unwrap(), "rust-2026-what-s-new-2.md");
+        assert_eq!(fs::read_to_string(first).unwrap(), fs::read_to_string(second).unwrap());
+    }
+}
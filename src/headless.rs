@@ -0,0 +1,152 @@
+use crate::config::{Config, FeedConfig};
+use chrono::{DateTime, Utc};
+use futures::future::join_all;
+use rss_tui::rss_funcs::{self, ClientPool, Feed, FetchError, Fetched, Validators};
+use serde::Serialize;
+use std::time::Duration;
+use unicode_width::UnicodeWidthStr;
+
+/// How one configured feed fared in `rss-tui check`.
+#[derive(Serialize)]
+struct FeedReport {
+    title: String,
+    url: String,
+    ok: bool,
+    items: usize,
+    error: Option<String>,
+}
+
+/// An item as `--dump-json` prints it.
+#[derive(Serialize)]
+struct DumpedItem<'a> {
+    title: &'a str,
+    date: Option<DateTime<Utc>>,
+    link: &'a str,
+    description: &'a str,
+    /// The feed's title from the config
+    source: &'a str,
+    source_url: &'a str,
+}
+
+/// Every feed fetched at once, as the TUI does at startup, with the same timeouts, proxies and
+/// retries. Results come back in config order.
+async fn fetch_all(config: &Config) -> Vec<(&FeedConfig, Result<Feed, FetchError>)> {
+    let clients = ClientPool::new(
+        Duration::from_secs(config.connect_timeout_secs),
+        Duration::from_secs(config.fetch_timeout_secs),
+        config.user_agent(),
+    );
+    join_all(config.feeds.iter().map(|feed| {
+        let client = clients.get(&config.proxy_for(&feed.url));
+        async move {
+            let result = match client {
+                Ok(client) => rss_funcs::get_feed_with_retry(&client, &feed.url, &Validators::default()).await,
+                Err(e) => Err(FetchError::Proxy(e.to_string())),
+            };
+            let result = match result {
+                Ok(Fetched::Modified(parsed, _)) => Ok(parsed),
+                // Nothing was cached, so a 304 is the server's doing
+                Ok(Fetched::NotModified) => Err(FetchError::Status(reqwest::StatusCode::NOT_MODIFIED)),
+                Err(e) => Err(e),
+            };
+            (feed, result)
+        }
+    }))
+    .await
+}
+
+/// `rss-tui check`: one line per feed (or a JSON array with `json`). Returns whether every feed came back fine.
+pub async fn check(config: &Config, json: bool) -> anyhow::Result<bool> {
+    let reports: Vec<FeedReport> = fetch_all(config)
+        .await
+        .into_iter()
+        .map(|(feed, result)| {
+            let (items, error) = match result {
+                Ok(parsed) => (parsed.items.len(), None),
+                Err(e) => (0, Some(e.to_string())),
+            };
+            FeedReport { title: feed.title.trim().to_string(), url: feed.url.clone(), ok: error.is_none(), items, error }
+        })
+        .collect();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+    } else {
+        let title_width = reports.iter().map(|r| r.title.width()).max().unwrap_or(0);
+        for report in &reports {
+            let padding = " ".repeat(title_width - report.title.width());
+            match &report.error {
+                None => {
+                    let noun = if report.items == 1 { "item" } else { "items" };
+                    println!("OK    {}{}  {} {}  {}", report.title, padding, report.items, noun, report.url)
+                }
+                Some(e) => println!("FAIL  {}{}  {}  {}", report.title, padding, e, report.url),
+            }
+        }
+        let failed = reports.iter().filter(|r| !r.ok).count();
+        println!("\n{} of {} feeds OK", reports.len() - failed, reports.len());
+    }
+    Ok(reports.iter().all(|r| r.ok))
+}
+
+/// `--dump-json`: every item of every feed as one JSON array, newest first. Feeds that fail are
+/// reported on stderr and left out, so the output stays valid JSON.
+pub async fn dump_json(config: &Config) -> anyhow::Result<()> {
+    let results = fetch_all(config).await;
+    let mut items = Vec::new();
+    for (feed, result) in &results {
+        match result {
+            Ok(parsed) => items.extend(dumped_items(feed, parsed)),
+            Err(e) => eprintln!("warning: {}: {}", feed.url, e),
+        }
+    }
+    items.sort_by_key(|item| std::cmp::Reverse(item.date));
+    println!("{}", serde_json::to_string_pretty(&items)?);
+    Ok(())
+}
+
+/// One feed's items as `--dump-json` prints them, tagged with the feed they came from.
+fn dumped_items<'a>(feed: &'a FeedConfig, parsed: &'a Feed) -> impl Iterator<Item = DumpedItem<'a>> {
+    parsed.items.iter().map(move |item| DumpedItem {
+        title: &item.title,
+        date: item.published,
+        link: &item.link,
+        description: &item.summary,
+        source: feed.title.trim(),
+        source_url: &feed.url,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use rss_tui::rss_funcs::FeedItem;
+
+    #[test]
+    fn dumped_items_serialize_with_their_source() {
+        let feed: FeedConfig = toml::from_str("url = \"https://example.com/rss\"\ntitle = \" EXAMPLE \"\ncolumn = 0\nslot = 0").unwrap();
+        let item = FeedItem {
+            title: "Headline".to_string(),
+            link: "https://example.com/1".to_string(),
+            summary: "Summary".to_string(),
+            published: Some(Utc.with_ymd_and_hms(2026, 3, 2, 12, 0, 0).unwrap()),
+            ..FeedItem::default()
+        };
+        let parsed = Feed { items: vec![item], ..Feed::default() };
+
+        let json = serde_json::to_string(&dumped_items(&feed, &parsed).collect::<Vec<_>>()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!([{
+                "title": "Headline",
+                "date": "2026-03-02T12:00:00Z",
+                "link": "https://example.com/1",
+                "description": "Summary",
+                "source": "EXAMPLE",
+                "source_url": "https://example.com/rss",
+            }])
+        );
+    }
+}
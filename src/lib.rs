@@ -0,0 +1,17 @@
+//! Fetching and parsing of RSS, Atom and JSON Feed documents, as used by the `rss-tui` binary.
+//!
+//! The format is detected from the content, so callers don't need to know what a URL serves:
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), rss_tui::FetchError> {
+//! let feed = rss_tui::fetch_feed("https://blog.rust-lang.org/feed.xml").await?;
+//! for item in &feed.items {
+//!     println!("{} ({})", item.title, item.link);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod rss_funcs;
+
+pub use rss_funcs::{fetch_feed, parse_feed, Feed, FeedItem, FetchError};
@@ -0,0 +1,26 @@
+use std::fs::OpenOptions;
+use std::io::{self, IsTerminal};
+use std::sync::Mutex;
+use tracing_subscriber::EnvFilter;
+
+// Used when RUST_LOG isn't set: the app's own events, and only warnings from its dependencies
+const DEFAULT_FILTER: &str = "warn,rss_tui=info";
+
+/// Sends `tracing` output to the `--log-file` path (appended to), or to stderr when that isn't
+/// a terminal. With neither, events are dropped: anything written to the terminal would
+/// end up drawn over the TUI.
+pub fn init() -> anyhow::Result<()> {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+    if let Some(path) = crate::config::arg_value("--log-file") {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| anyhow::anyhow!("Couldn't open log file {}: {}", path, e))?;
+        builder.with_ansi(false).with_writer(Mutex::new(file)).init();
+    } else if !io::stderr().is_terminal() {
+        builder.with_ansi(false).with_writer(io::stderr).init();
+    }
+    Ok(())
+}
@@ -1,238 +1,2554 @@
 use crossterm::{
-    event::{self, KeyCode, KeyEventKind},
+    event::{
+        self, DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture, KeyCode, KeyEventKind,
+        MouseButton, MouseEvent, MouseEventKind,
+    },
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     ExecutableCommand,
 };
+use chrono::{DateTime, Local, Utc};
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, List, ListItem, BorderType, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, BorderType, Paragraph, Wrap},
 };
 use std::env;
 use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
-use std::io::stdout;
+use std::fs::OpenOptions;
+use std::io::{stdout, Write};
+use std::path::Path;
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
-use std::collections::BTreeMap;
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::cell::{Cell, RefCell};
+use std::sync::Arc;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+use image::DynamicImage;
+use ratatui_image::{picker::Picker, picker::ProtocolType, protocol::StatefulProtocol, StatefulImage};
 
-mod rss_funcs;
+mod actions;
+mod cache;
+mod config;
+mod export;
+mod headless;
+mod logging;
+mod notify;
+mod opml;
+mod state;
+mod store;
 mod telegram_funcs;
-use telegram_funcs::TelegramMonitor;
+mod theme;
+use actions::{Action, Keymap};
+use cache::FeedCache;
+use config::{Config, EmptyPolicy, ErrorDisplay, FooterOverflow, Rotation, TelegramPanel, TitleOverflow};
+use rss_tui::rss_funcs::{self, ClientPool, Feed, FeedItem, FetchError, Fetched, Validators};
+use state::{SavedView, UiState};
+use store::{Bookmark, ReadStore};
+use telegram_funcs::{ConnectionState, ReauthRequest, TelegramMessage, TelegramMonitor};
+use theme::{ColorLevel, Theme};
 
 // --- UI Constants ---
-const DARK_BG: Color = Color::Rgb(15, 15, 20);
-const BORDER_MUTED: Color = Color::Rgb(50, 50, 60); 
+// Accents of the built-in feeds; everything else comes from the active Theme
 const MATRIX_GREEN: Color = Color::Rgb(0, 235, 65);
 const NEWS_GOLD: Color = Color::Rgb(255, 170, 50);
 const SPORTS_CYAN: Color = Color::Rgb(0, 255, 255);
 const WORLD_MAGENTA: Color = Color::Rgb(255, 0, 255);
-const TELEGRAM_BLUE: Color = Color::Rgb(0, 136, 204);
-const DESC_GREY: Color = Color::Rgb(120, 120, 130);
-const UI_GREY: Color = Color::Rgb(160, 160, 170);
+
+// Telegram messages kept in memory, and shown per panel (newest first)
+const TELEGRAM_HISTORY: usize = 200;
+const TELEGRAM_SHOWN: usize = 20;
+// Minimum gap between refreshes triggered by regaining terminal focus
+const FOCUS_REFRESH_DEBOUNCE: Duration = Duration::from_secs(30);
+// Tallest an inline image gets in the reading pane
+const READER_IMAGE_ROWS: u16 = 12;
+// How long quitting waits for background tasks to wind down before leaving them behind
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(3);
+// Wait after a 429 that didn't say how long to back off
+const RATE_LIMIT_FALLBACK_WAIT: Duration = Duration::from_secs(60);
+// Under the empty policy, a refresh with less than half the items of the last one counts as empty,
+// once the feed has had at least this many
+const SHRINK_MIN_ITEMS: usize = 4;
+// Below this many columns or rows only a "terminal too small" message is drawn
+const MIN_COLUMNS: u16 = 40;
+const MIN_ROWS: u16 = 12;
+// How long a footer notice such as "copied link" stays up
+const NOTICE_DURATION: Duration = Duration::from_secs(3);
+// Log entries kept in memory (oldest dropped first), and rows the log panel takes when shown
+const LOG_CAPACITY: usize = 500;
+const LOG_PANEL_ROWS: u16 = 8;
+// Loading spinner, one frame per SPINNER_FRAME however often the loop happens to redraw
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+const SPINNER_FRAME: Duration = Duration::from_millis(100);
+
+/// A panel that can take keyboard focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Panel {
+    Feed(usize),
+    Telegram(usize),
+}
+
+/// What the mouse is over, as recorded while drawing the last frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Hit {
+    Feed(usize),
+    // One item of a feed block, as an index into the feed's visible items
+    FeedItem(usize, usize),
+    Telegram(usize),
+    // A row of the timeline or starred list
+    ListRow(usize),
+}
+
+/// Where each feed's most recent fetch stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FetchState {
+    Idle,
+    Loading,
+    Loaded,
+    Error,
+}
+
+/// The feed grid, one feed filling the window, or every feed merged by date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ViewMode {
+    Grid,
+    Focused(usize),
+    Timeline,
+    Bookmarks,
+}
+
+/// Command palette state: the typed filter and the highlighted match.
+#[derive(Default)]
+struct Palette {
+    query: String,
+    selected: usize,
+}
+
+/// Full-text view of one item. Scrolling is capped by what the last render could show.
+struct Reader {
+    item: FeedItem,
+    scroll: u16,
+    max_scroll: Cell<u16>,
+    // Set once the item's image has downloaded, on terminals that can draw it; resized while rendering
+    image: Option<RefCell<StatefulProtocol>>,
+}
+
+/// A short message in the footer, e.g. after copying a link.
+struct Notice {
+    text: String,
+    is_error: bool,
+    until: Instant,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+/// One line of the log panel: fetch results, Telegram state changes, actions taken.
+struct LogEntry {
+    at: DateTime<Local>,
+    level: LogLevel,
+    text: String,
+}
+
+/// Background tasks and the signal that stops them, so quitting can wait for them to finish.
+#[derive(Clone, Default)]
+struct Shutdown {
+    token: CancellationToken,
+    tasks: TaskTracker,
+}
+
+impl Shutdown {
+    /// Runs `task` in the background until it finishes or shutdown begins, whichever is first.
+    fn spawn(&self, task: impl Future<Output = ()> + Send + 'static) {
+        let token = self.token.clone();
+        self.tasks.spawn(async move {
+            tokio::select! {
+                _ = token.cancelled() => {}
+                _ = task => {}
+            }
+        });
+    }
+
+    /// Cancels everything and waits (up to `SHUTDOWN_GRACE`) for the tasks to return.
+    async fn finish(&self) {
+        self.token.cancel();
+        self.tasks.close();
+        let _ = tokio::time::timeout(SHUTDOWN_GRACE, self.tasks.wait()).await;
+    }
+}
+
+/// Source of "now" for time-dependent UI logic; swap it out to drive the app deterministically.
+type Clock = fn() -> DateTime<Local>;
 
 struct App {
-    rss_feeds: Vec<Vec<(String, String, String)>>, 
-    telegram_messages: BTreeMap<String, String>, 
-    tx: mpsc::UnboundedSender<Vec<Vec<(String, String, String)>>>,
-    rx: mpsc::UnboundedReceiver<Vec<Vec<(String, String, String)>>>,
-    tg_rx: mpsc::UnboundedReceiver<(String, String)>, 
-    offset: usize,
+    rss_feeds: Vec<Feed>, 
+    // Latest message per sender
+    // Oldest first, capped at TELEGRAM_HISTORY
+    telegram_messages: VecDeque<TelegramMessage>,
+    // When each chat was last looked at in a focused Telegram panel; newer messages count as unread
+    telegram_read_at: HashMap<i64, DateTime<Utc>>,
+    tx: mpsc::UnboundedSender<(usize, Result<Fetched, FetchError>)>,
+    rx: mpsc::UnboundedReceiver<(usize, Result<Fetched, FetchError>)>,
+    tg_rx: mpsc::UnboundedReceiver<TelegramMessage>, 
+    // Fetches and the Telegram tasks; all stopped and awaited on quit
+    shutdown: Shutdown,
+    page_tx: mpsc::UnboundedSender<(usize, Option<Feed>)>,
+    page_rx: mpsc::UnboundedReceiver<(usize, Option<Feed>)>,
+    // Downloaded reader images, keyed by URL; None when the download or decode failed
+    image_tx: mpsc::UnboundedSender<(String, Option<DynamicImage>)>,
+    image_rx: mpsc::UnboundedReceiver<(String, Option<DynamicImage>)>,
+    // Graphics protocol for inline images; None on terminals without sixel, kitty or iTerm2 support
+    image_picker: Option<Picker>,
+    offsets: Vec<usize>,
+    loading_more: Vec<bool>,
+    stale: Vec<bool>,
+    // Items in each feed's last non-empty response, not counting older pages appended since
+    response_len: Vec<usize>,
+    errors: Vec<Option<String>>,
+    // Showing the on-disk copy because the latest fetch couldn't reach the host
+    offline: Vec<bool>,
+    // None keeps no offline copies
+    cache: Option<FeedCache>,
+    fetched_at: Vec<Option<DateTime<Local>>>,
+    fetch_state: Vec<FetchState>,
+    // Feeds re-fetched by [F] whose results haven't come back yet
+    retrying: HashSet<usize>,
+    // Set from a 429's Retry-After; no fetches for that feed before then
+    rate_limited_until: Vec<Option<DateTime<Local>>>,
+    // The spinner frame is picked from the time since this, not from how many passes the loop made
+    spinner_started: Instant,
+    // Item keys in each feed's latest response (plus pages loaded since), and the ones that refresh added
+    seen: Vec<HashSet<String>>,
+    new_items: Vec<HashSet<String>>,
+    // Keys of items opened in the reader, this run and (via the store) earlier ones
+    read: HashSet<String>,
+    store: Option<ReadStore>,
+    // ETag / Last-Modified of each feed's last full response, keyed by feed URL
+    validators: HashMap<String, Validators>,
+    // Feed whose headline digest overlay is open
+    digest: Option<usize>,
+    reader: Option<Reader>,
+    palette: Option<Palette>,
+    // Key binding overlay from [?]
+    help: bool,
+    view: ViewMode,
+    // Line scroll of the full-screen feed, capped by what the last render could show
+    zoom_scroll: u16,
+    zoom_max_scroll: Cell<u16>,
+    // Items per feed block as set with [+]/[-]; None packs each block as full as its height allows
+    items_per_block: Option<usize>,
+    // Items each feed block had room for at the last render
+    fits: Vec<Cell<usize>>,
+    // Highlighted row of the merged timeline
+    timeline_selected: usize,
+    // Starred items, most recently starred first, and the highlighted row of their list
+    bookmarks: Vec<Bookmark>,
+    bookmarks_selected: usize,
+    // Messages skipped from the top of each Telegram panel, i.e. how far it's scrolled back
+    telegram_scroll: Vec<usize>,
+    // Filter for the Telegram column, and whether keys are currently typing into it
+    telegram_query: String,
+    telegram_query_editing: bool,
+    // Only items in this category (or from feeds tagged with it) are listed; cycled with [c]
+    category_filter: Option<String>,
+    // Search over every feed's titles and descriptions
+    feed_query: String,
+    feed_query_editing: bool,
+    clients: Arc<ClientPool>,
+    ui_state: UiState,
+    focused: Option<Panel>,
+    // Screen areas of the last frame and what they show, innermost last, for mouse clicks
+    hits: RefCell<Vec<(Rect, Hit)>>,
+    // Set by a resize; positions are clamped once a frame at the new size has been measured
+    resized: bool,
+    // Focus taken by auto_focus_new and when to hand it back
+    auto_focus_until: Option<DateTime<Local>>,
+    sort_by_score: bool,
+    // Rotation held with Space/p; refreshes carry on
+    paused: bool,
+    window_focused: bool,
+    last_focus_refresh: Option<Instant>,
+    // Start of the current automatic refresh countdown
+    last_refresh: Instant,
+    telegram_configured: bool,
+    // Set by --no-telegram: no login, no monitor, no column
+    telegram_disabled: bool,
+    telegram_offline: bool,
+    telegram_error: Option<String>,
+    // Latest word from the monitor; None until one has started
+    telegram_status: Option<ConnectionState>,
+    config: Config,
+    theme: Theme,
+    // Name (or file path) the theme was loaded from, so [C] knows where to continue
+    theme_name: String,
+    // Opened on first use and kept, since on X11 the copied text lives only as long as the handle
+    clipboard: Option<arboard::Clipboard>,
+    notice: Option<Notice>,
+    // Oldest first, capped at LOG_CAPACITY; shown in the bottom panel toggled with [l]
+    log: VecDeque<LogEntry>,
+    show_log: bool,
+    clock: Clock,
 }
 
 impl App {
     fn new(
-        tx: mpsc::UnboundedSender<Vec<Vec<(String, String, String)>>>, 
-        rx: mpsc::UnboundedReceiver<Vec<Vec<(String, String, String)>>>,
-        tg_rx: mpsc::UnboundedReceiver<(String, String)>,
+        tx: mpsc::UnboundedSender<(usize, Result<Fetched, FetchError>)>, 
+        rx: mpsc::UnboundedReceiver<(usize, Result<Fetched, FetchError>)>,
+        tg_rx: mpsc::UnboundedReceiver<TelegramMessage>,
+        shutdown: Shutdown,
+        ui_state: UiState,
+        config: Config,
+        theme: Theme,
     ) -> Self {
+        // Without a usable database, read marks just last for this run
+        let store = ReadStore::open_default().ok();
+        Self::with_clock(tx, rx, tg_rx, shutdown, ui_state, config, theme, store, Some(FeedCache::open_default()), Local::now)
+    }
+
+    /// `new` with the read store, the offline cache and the source of "now" supplied, so time-dependent behaviour
+    /// can be pinned down.
+    #[allow(clippy::too_many_arguments)]
+    fn with_clock(
+        tx: mpsc::UnboundedSender<(usize, Result<Fetched, FetchError>)>,
+        rx: mpsc::UnboundedReceiver<(usize, Result<Fetched, FetchError>)>,
+        tg_rx: mpsc::UnboundedReceiver<TelegramMessage>,
+        shutdown: Shutdown,
+        ui_state: UiState,
+        config: Config,
+        theme: Theme,
+        store: Option<ReadStore>,
+        cache: Option<FeedCache>,
+        clock: Clock,
+    ) -> Self {
+        let (page_tx, page_rx) = mpsc::unbounded_channel();
+        let (image_tx, image_rx) = mpsc::unbounded_channel();
+        let clients = ClientPool::new(
+            Duration::from_secs(config.connect_timeout_secs),
+            Duration::from_secs(config.fetch_timeout_secs),
+            config.user_agent(),
+        );
+        let feed_count = config.feeds.len();
+        let theme_name = config.theme.clone();
+        if let Some(store) = &store {
+            let _ = store.prune(config.read_retention_days, clock().with_timezone(&Utc));
+        }
+        let read = store.as_ref().and_then(|s| s.read_keys().ok()).unwrap_or_default();
+        let bookmarks = store.as_ref().and_then(|s| s.bookmarks().ok()).unwrap_or_default();
         Self {
-            // 0-2: Left Column | 3-5: Middle Column
-            rss_feeds: vec![vec![]; 6],
-            telegram_messages: BTreeMap::new(),
+            rss_feeds: vec![Feed::default(); feed_count],
+            telegram_messages: VecDeque::new(),
+            telegram_read_at: HashMap::new(),
             tx,
             rx,
             tg_rx,
-            offset: 0,
+            shutdown,
+            page_tx,
+            page_rx,
+            image_tx,
+            image_rx,
+            image_picker: None,
+            offsets: vec![0; feed_count],
+            loading_more: vec![false; feed_count],
+            stale: vec![false; feed_count],
+            response_len: vec![0; feed_count],
+            errors: vec![None; feed_count],
+            offline: vec![false; feed_count],
+            cache,
+            fetched_at: vec![None; feed_count],
+            fetch_state: vec![FetchState::Idle; feed_count],
+            retrying: HashSet::new(),
+            rate_limited_until: vec![None; feed_count],
+            spinner_started: Instant::now(),
+            seen: vec![HashSet::new(); feed_count],
+            new_items: vec![HashSet::new(); feed_count],
+            read,
+            store,
+            validators: HashMap::new(),
+            digest: None,
+            reader: None,
+            palette: None,
+            help: false,
+            view: ViewMode::Grid,
+            zoom_scroll: 0,
+            zoom_max_scroll: Cell::new(0),
+            items_per_block: None,
+            fits: (0..feed_count).map(|_| Cell::new(1)).collect(),
+            timeline_selected: 0,
+            bookmarks,
+            bookmarks_selected: 0,
+            telegram_scroll: vec![0; config.telegram_panels().len()],
+            telegram_query: String::new(),
+            telegram_query_editing: false,
+            category_filter: None,
+            feed_query: String::new(),
+            feed_query_editing: false,
+            clients: Arc::new(clients),
+            ui_state,
+            focused: None,
+            hits: RefCell::new(Vec::new()),
+            resized: false,
+            auto_focus_until: None,
+            sort_by_score: false,
+            paused: false,
+            window_focused: true,
+            last_focus_refresh: None,
+            last_refresh: Instant::now(),
+            telegram_configured: false,
+            telegram_disabled: false,
+            telegram_offline: false,
+            telegram_error: None,
+            telegram_status: None,
+            config,
+            theme,
+            theme_name,
+            clipboard: None,
+            notice: None,
+            log: VecDeque::new(),
+            show_log: false,
+            clock,
+        }
+    }
+
+    fn now(&self) -> DateTime<Local> {
+        (self.clock)()
+    }
+
+    /// The Telegram column is drawn unless hidden with [t] or switched off by `--no-telegram`.
+    fn telegram_visible(&self) -> bool {
+        self.ui_state.show_telegram && !self.telegram_disabled
+    }
+
+    /// A feed's configured colour, else the theme palette colour for its position in the config.
+    fn feed_color(&self, feed_idx: usize) -> Color {
+        self.config.feeds[feed_idx].color.unwrap_or_else(|| self.theme.feed_color(feed_idx))
+    }
+
+    /// Tab walks the feed panels, then any visible Telegram panels, then back to no focus.
+    fn cycle_focus(&mut self) {
+        self.auto_focus_until = None;
+        let telegram_panels = if self.telegram_visible() { self.config.telegram_panels().len() } else { 0 };
+        self.focused = match self.focused {
+            None if !self.config.feeds.is_empty() => Some(Panel::Feed(0)),
+            None if telegram_panels > 0 => Some(Panel::Telegram(0)),
+            Some(Panel::Feed(i)) if i + 1 < self.config.feeds.len() => Some(Panel::Feed(i + 1)),
+            Some(Panel::Feed(_)) if telegram_panels > 0 => Some(Panel::Telegram(0)),
+            Some(Panel::Telegram(i)) if i + 1 < telegram_panels => Some(Panel::Telegram(i + 1)),
+            _ => None,
+        };
+    }
+
+    fn focused_feed(&self) -> Option<usize> {
+        match self.focused {
+            Some(Panel::Feed(idx)) => Some(idx),
+            _ => None,
+        }
+    }
+
+    /// Items of a feed in display order: source order, or highest score first.
+    /// An active search leaves only the matching items.
+    fn visible_items(&self, feed_idx: usize) -> Vec<&FeedItem> {
+        let query = self.feed_query.to_lowercase();
+        let mut items: Vec<_> = self.rss_feeds[feed_idx]
+            .items
+            .iter()
+            .filter(|item| {
+                query.is_empty()
+                    || item.title.to_lowercase().contains(&query)
+                    || item.summary.to_lowercase().contains(&query)
+            })
+            .filter(|item| self.matches_category(feed_idx, item))
+            .collect();
+        if self.sort_by_score {
+            let url = &self.config.feeds[feed_idx].url;
+            items.sort_by_key(|item| std::cmp::Reverse(self.config.score(url, &item.title, &item.summary)));
+        }
+        items
+    }
+
+    /// Passes everything without a category filter; otherwise the item, or its feed's tag, must carry it.
+    fn matches_category(&self, feed_idx: usize, item: &FeedItem) -> bool {
+        let Some(filter) = &self.category_filter else { return true };
+        self.config.feeds[feed_idx].category.as_ref().is_some_and(|tag| tag.eq_ignore_ascii_case(filter))
+            || item.categories.iter().any(|c| c.eq_ignore_ascii_case(filter))
+    }
+
+    /// Every category among the loaded items and feed tags, alphabetically, ignoring case.
+    fn categories(&self) -> Vec<String> {
+        let mut all: Vec<String> = self
+            .config
+            .feeds
+            .iter()
+            .filter_map(|feed| feed.category.clone())
+            .chain(self.rss_feeds.iter().flat_map(|feed| feed.items.iter().flat_map(|item| item.categories.iter().cloned())))
+            .collect();
+        all.sort_by_key(|c| c.to_lowercase());
+        all.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+        all
+    }
+
+    /// Steps the category filter through `categories()`, then back to showing everything.
+    fn cycle_category(&mut self) {
+        let categories = self.categories();
+        let next = match &self.category_filter {
+            None => 0,
+            Some(current) => categories.iter().position(|c| c.eq_ignore_ascii_case(current)).map_or(0, |i| i + 1),
+        };
+        self.category_filter = categories.get(next).cloned();
+        self.offsets.iter_mut().for_each(|offset| *offset = 0);
+    }
+
+    /// Items the feed's block shows: as many as it had room for at the last render, or fewer if
+    /// set with [-]. Never less than one.
+    fn shown_items(&self, feed_idx: usize) -> usize {
+        let fits = self.fits[feed_idx].get();
+        self.items_per_block.map_or(fits, |n| n.min(fits)).max(1)
+    }
+
+    /// [+]/[-]: one item more or fewer per block, measured against the roomiest block on screen.
+    /// Asking for more than any block can fit goes back to fitting each block to its height.
+    fn change_items_per_block(&mut self, more: bool) {
+        let most = self.fits.iter().map(Cell::get).max().unwrap_or(1).max(1);
+        let current = self.items_per_block.map_or(most, |n| n.min(most));
+        self.items_per_block = match more {
+            true if current >= most => None,
+            true => Some(current + 1),
+            false => Some(current.saturating_sub(1).max(1)),
+        };
+        let text = match self.items_per_block {
+            Some(n) => format!("{} items per block", n),
+            None => "Items per block fit to height".to_string(),
+        };
+        self.show_notice(text, false);
+    }
+
+    /// True when the last item of the feed is on screen.
+    fn at_feed_end(&self, feed_idx: usize) -> bool {
+        let len = self.rss_feeds[feed_idx].items.len();
+        len > 0 && self.offsets[feed_idx] % len + self.shown_items(feed_idx) >= len
+    }
+
+    /// Fetches the focused feed's next page once its oldest item is showing.
+    fn load_more(&mut self) {
+        let Some(idx) = self.focused_feed() else { return };
+        let Some(next) = self.rss_feeds[idx].next_page.clone() else { return };
+        if self.loading_more[idx] || !self.at_feed_end(idx) {
+            return;
+        }
+        self.loading_more[idx] = true;
+        let tx = self.page_tx.clone();
+        // Later pages go through the same proxy as the feed itself
+        let client = self.clients.get(&self.config.proxy_for(&self.config.feeds[idx].url));
+        self.shutdown.spawn(async move {
+            let page = match client {
+                Ok(client) => match rss_funcs::get_feed(&client, &next, &Validators::default()).await {
+                    Ok(Fetched::Modified(page, _)) => Some(page),
+                    _ => None,
+                },
+                Err(_) => None,
+            };
+            let _ = tx.send((idx, page));
+        });
+    }
+
+    /// Manual browsing of the focused feed; rotation leaves that feed alone while it has focus.
+    fn scroll_focused(&mut self, down: bool) {
+        if let Some(Panel::Telegram(panel_idx)) = self.focused {
+            self.scroll_telegram(panel_idx, down);
+            return;
+        }
+        let Some(idx) = self.focused_feed() else { return };
+        let len = self.rss_feeds[idx].items.len();
+        if len == 0 {
+            return;
+        }
+        let offset = self.offsets[idx] % len;
+        self.offsets[idx] = if down {
+            (offset + 1).min(len.saturating_sub(self.shown_items(idx)))
+        } else {
+            offset.saturating_sub(1)
+        };
+    }
+
+    fn append_page(&mut self, idx: usize, page: Option<Feed>) {
+        self.loading_more[idx] = false;
+        if let Some(page) = page {
+            let feed = &mut self.rss_feeds[idx];
+            // Pages can overlap when the feed shifts between requests
+            let seen = &mut self.seen[idx];
+            feed.items.extend(page.items.into_iter().filter(|item| seen.insert(item.key().to_string())));
+            feed.next_page = page.next_page;
+        }
+    }
+
+    fn push_telegram(&mut self, msg: TelegramMessage) {
+        if self.telegram_messages.len() == TELEGRAM_HISTORY {
+            self.telegram_messages.pop_front();
+        }
+        // A panel scrolled back keeps showing the same messages as new ones arrive on top
+        for (i, panel) in self.config.telegram_panels().iter().enumerate() {
+            if self.telegram_scroll[i] > 0 && panel.accepts(msg.chat_id) {
+                self.telegram_scroll[i] += 1;
+            }
+        }
+        self.telegram_messages.push_back(msg);
+    }
+
+    /// Everything in the focused Telegram panel counts as read; called before each frame.
+    fn mark_telegram_read(&mut self) {
+        let Some(Panel::Telegram(panel_idx)) = self.focused else { return };
+        let Some(panel) = self.config.telegram_panels().into_iter().nth(panel_idx) else { return };
+        let now = self.now().with_timezone(&Utc);
+        for msg in self.telegram_messages.iter().filter(|m| panel.accepts(m.chat_id)) {
+            self.telegram_read_at.insert(msg.chat_id, now);
+        }
+    }
+
+    /// Chats with a message newer than the last time their panel had focus.
+    fn unread_chats(&self) -> usize {
+        let unread: HashSet<i64> = self
+            .telegram_messages
+            .iter()
+            .filter(|m| self.telegram_read_at.get(&m.chat_id).is_none_or(|&read| m.timestamp > read))
+            .map(|m| m.chat_id)
+            .collect();
+        unread.len()
+    }
+
+    /// A panel's messages that pass the Telegram filter, newest first.
+    fn panel_messages(&self, panel: &TelegramPanel) -> Vec<&TelegramMessage> {
+        let query = self.telegram_query.to_lowercase();
+        self.telegram_messages
+            .iter()
+            .rev()
+            .filter(|m| panel.accepts(m.chat_id))
+            .filter(|m| query.is_empty() || m.sender.to_lowercase().contains(&query) || m.text.to_lowercase().contains(&query))
+            .collect()
+    }
+
+    /// Moves a Telegram panel back through older messages (down) or towards the newest (up).
+    fn scroll_telegram(&mut self, panel_idx: usize, down: bool) {
+        let Some(panel) = self.config.telegram_panels().into_iter().nth(panel_idx) else { return };
+        let last = self.panel_messages(&panel).len().saturating_sub(1);
+        let scroll = &mut self.telegram_scroll[panel_idx];
+        *scroll = if down { (*scroll + 1).min(last) } else { scroll.saturating_sub(1) };
+    }
+
+    /// Appends to the configured JSONL archive; failures are shown in the Telegram column.
+    fn log_telegram(&mut self, msg: &TelegramMessage) {
+        let Some(path) = &self.config.telegram_log else { return };
+        let result = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| writeln!(file, "{}", serde_json::to_string(msg).unwrap_or_default()));
+        if let Err(e) = result {
+            let text = format!("Log write failed: {}", e);
+            self.log(LogLevel::Error, format!("Telegram: {}", text));
+            self.telegram_error = Some(text);
+        }
+    }
+
+    fn perform(&mut self, action: Action) {
+        // Scrolling would drown out everything else
+        if !matches!(action, Action::ScrollUp | Action::ScrollDown) {
+            self.log(LogLevel::Info, action.name());
+        }
+        match action {
+            Action::Refresh => self.fetch_rss(),
+            Action::RefreshFocused => {
+                if let Some(idx) = self.focused_feed() {
+                    self.fetch_feed(idx);
+                }
+            }
+            Action::RetryFailed => self.retry_failed(),
+            Action::MarkAllRead => self.mark_all_read(),
+            Action::CycleCategory => self.cycle_category(),
+            Action::NextPanel => self.cycle_focus(),
+            Action::Close if self.help => self.help = false,
+            Action::Close if self.reader.is_some() => self.reader = None,
+            Action::Close if self.digest.is_some() => self.digest = None,
+            Action::Close if !self.feed_query.is_empty() => self.feed_query.clear(),
+            Action::ScrollUp | Action::ScrollDown if self.reader.is_some() => self.scroll_reader(action == Action::ScrollDown),
+            Action::Close if self.view != ViewMode::Grid => self.view = ViewMode::Grid,
+            Action::ScrollUp | Action::ScrollDown if self.view == ViewMode::Timeline => {
+                self.move_timeline_selection(action == Action::ScrollDown)
+            }
+            Action::ScrollUp | Action::ScrollDown if self.view == ViewMode::Bookmarks => {
+                self.move_bookmark_selection(action == Action::ScrollDown)
+            }
+            Action::ReadItem if self.view == ViewMode::Bookmarks => self.open_bookmark(),
+            Action::ScrollUp | Action::ScrollDown if self.view != ViewMode::Grid => self.scroll_zoom(action == Action::ScrollDown),
+            Action::Zoom => match (self.view, self.focused_feed()) {
+                (ViewMode::Focused(_), _) => self.view = ViewMode::Grid,
+                (_, Some(idx)) => self.zoom(idx),
+                (_, None) => {}
+            },
+            Action::Timeline => self.toggle_timeline(),
+            Action::MoreItems => self.change_items_per_block(true),
+            Action::FewerItems => self.change_items_per_block(false),
+            Action::Bookmarks => self.toggle_bookmarks(),
+            Action::ToggleBookmark => self.toggle_bookmark(),
+            Action::Close => {
+                self.focused = None;
+                self.auto_focus_until = None;
+            }
+            Action::OpenHomepage => self.open_feed_homepage(),
+            Action::LoadMore => self.load_more(),
+            Action::ToggleScoreSort => self.sort_by_score = !self.sort_by_score,
+            Action::TogglePause => self.paused = !self.paused,
+            Action::Digest => self.toggle_digest(),
+            Action::Help => self.help = !self.help,
+            Action::ToggleLog => self.show_log = !self.show_log,
+            Action::CommandPalette => self.palette = Some(Palette::default()),
+            Action::ReadItem => self.open_reader(),
+            Action::CopyLink => self.copy_link(),
+            Action::SaveItem => self.save_item(),
+            Action::CycleTheme => {
+                let (name, theme) = Theme::next_built_in(&self.theme_name);
+                self.theme_name = name.to_string();
+                self.ui_state.theme = Some(name.to_string());
+                self.theme = theme.with_level(self.theme.level);
+            }
+            Action::ScrollUp => self.scroll_focused(false),
+            Action::ScrollDown => self.scroll_focused(true),
+            Action::Search => {
+                if matches!(self.focused, Some(Panel::Telegram(_))) {
+                    self.telegram_query_editing = true;
+                } else {
+                    self.feed_query_editing = true;
+                }
+            }
+            Action::ToggleTelegram if !self.telegram_disabled => {
+                // The monitor keeps running; messages pile up while hidden
+                self.ui_state.show_telegram = !self.ui_state.show_telegram;
+                if let Err(e) = self.ui_state.save() {
+                    self.log(LogLevel::Warn, format!("UI state not saved: {}", e));
+                }
+            }
+            Action::ToggleTelegram => {}
+            // Handled by the main loop, which owns the terminal
+            Action::Quit | Action::ConnectTelegram => {}
+        }
+    }
+
+    /// Typing into the Telegram filter: Enter keeps it, Esc clears it.
+    fn telegram_query_key(&mut self, code: KeyCode) {
+        edit_query(&mut self.telegram_query, &mut self.telegram_query_editing, code);
+    }
+
+    /// Same editing keys for the feed search; rotation restarts from the top as the matches change.
+    fn feed_query_key(&mut self, code: KeyCode) {
+        edit_query(&mut self.feed_query, &mut self.feed_query_editing, code);
+        self.offsets.iter_mut().for_each(|offset| *offset = 0);
+    }
+
+    fn feed_matches(&self) -> usize {
+        (0..self.rss_feeds.len()).map(|idx| self.visible_items(idx).len()).sum()
+    }
+
+    /// Edits the palette query; returns the chosen action on Enter.
+    fn palette_key(&mut self, code: KeyCode) -> Option<Action> {
+        let palette = self.palette.as_mut()?;
+        match code {
+            KeyCode::Esc => self.palette = None,
+            KeyCode::Enter => {
+                let chosen = actions::filter_actions(&palette.query).get(palette.selected).copied();
+                self.palette = None;
+                return chosen;
+            }
+            KeyCode::Up => palette.selected = palette.selected.saturating_sub(1),
+            KeyCode::Down => {
+                let count = actions::filter_actions(&palette.query).len();
+                palette.selected = (palette.selected + 1).min(count.saturating_sub(1));
+            }
+            KeyCode::Backspace => {
+                palette.query.pop();
+                palette.selected = 0;
+            }
+            KeyCode::Char(c) => {
+                palette.query.push(c);
+                palette.selected = 0;
+            }
+            _ => {}
         }
+        None
+    }
+
+    /// The item at the top of the focused feed (or highlighted in the timeline), with its feed;
+    /// what Enter opens and y copies.
+    fn focused_item(&self) -> Option<(usize, &FeedItem)> {
+        if self.view == ViewMode::Timeline {
+            let timeline = self.timeline();
+            // A selection restored from last run may point past a shorter timeline
+            return timeline.get(self.timeline_selected.min(timeline.len().saturating_sub(1))).copied();
+        }
+        let idx = self.focused_feed()?;
+        let items = self.visible_items(idx);
+        if items.is_empty() {
+            return None;
+        }
+        Some((idx, items[self.offsets[idx] % items.len()]))
+    }
+
+    /// Every feed's visible items with the feed they came from, newest first; undated items go last.
+    fn timeline(&self) -> Vec<(usize, &FeedItem)> {
+        let mut items: Vec<_> = (0..self.rss_feeds.len())
+            .flat_map(|idx| self.visible_items(idx).into_iter().map(move |item| (idx, item)))
+            .collect();
+        items.sort_by_key(|(_, item)| std::cmp::Reverse(item.published));
+        items
+    }
+
+    fn toggle_timeline(&mut self) {
+        self.view = if self.view == ViewMode::Timeline { ViewMode::Grid } else { ViewMode::Timeline };
+        self.timeline_selected = 0;
+    }
+
+    fn move_timeline_selection(&mut self, down: bool) {
+        let last = self.timeline().len().saturating_sub(1);
+        let current = self.timeline_selected.min(last);
+        self.timeline_selected = if down { (current + 1).min(last) } else { current.saturating_sub(1) };
+    }
+
+    fn toggle_bookmarks(&mut self) {
+        self.view = if self.view == ViewMode::Bookmarks { ViewMode::Grid } else { ViewMode::Bookmarks };
+        self.bookmarks_selected = 0;
+    }
+
+    fn move_bookmark_selection(&mut self, down: bool) {
+        let last = self.bookmarks.len().saturating_sub(1);
+        let current = self.bookmarks_selected.min(last);
+        self.bookmarks_selected = if down { (current + 1).min(last) } else { current.saturating_sub(1) };
+    }
+
+    fn is_bookmarked(&self, feed_idx: usize, item: &FeedItem) -> bool {
+        let url = &self.config.feeds[feed_idx].url;
+        self.bookmarks.iter().any(|b| b.feed_url == *url && b.key == item.key())
+    }
+
+    /// Stars or unstars the focused item; in the bookmarks view, unstars the highlighted one.
+    fn toggle_bookmark(&mut self) {
+        let (feed_url, key) = if self.view == ViewMode::Bookmarks {
+            let Some(b) = self.bookmarks.get(self.bookmarks_selected) else { return };
+            (b.feed_url.clone(), b.key.clone())
+        } else {
+            let Some((idx, item)) = self.focused_item() else { return };
+            (self.config.feeds[idx].url.clone(), item.key().to_string())
+        };
+        if let Some(pos) = self.bookmarks.iter().position(|b| b.feed_url == feed_url && b.key == key) {
+            let bookmark = self.bookmarks.remove(pos);
+            self.bookmarks_selected = self.bookmarks_selected.min(self.bookmarks.len().saturating_sub(1));
+            if let Some(Err(e)) = self.store.as_ref().map(|store| store.remove_bookmark(&feed_url, &key)) {
+                self.log(LogLevel::Warn, format!("Unstar not saved: {}", e));
+            }
+            self.show_notice(format!("unstarred {}", bookmark.title.trim()), false);
+            return;
+        }
+        let Some((_, item)) = self.focused_item() else { return };
+        let bookmark = Bookmark {
+            feed_url,
+            key,
+            title: item.title.clone(),
+            link: item.link.clone(),
+            published: item.published,
+            starred_at: self.now().with_timezone(&Utc),
+        };
+        // Without a usable database the star just lasts for this run
+        if let Some(Err(e)) = self.store.as_ref().map(|store| store.add_bookmark(&bookmark)) {
+            self.log(LogLevel::Warn, format!("Star not saved: {}", e));
+        }
+        self.show_notice(format!("starred {}", bookmark.title.trim()), false);
+        self.bookmarks.insert(0, bookmark);
+    }
+
+    /// The highlighted bookmark's article in the browser; the feed may no longer carry it.
+    fn open_bookmark(&self) {
+        let Some(bookmark) = self.bookmarks.get(self.bookmarks_selected.min(self.bookmarks.len().saturating_sub(1))) else {
+            return;
+        };
+        if !bookmark.link.is_empty() {
+            let _ = open::that_detached(&bookmark.link);
+        }
+    }
+
+    fn mark_read(&mut self, key: &str) {
+        if self.read.insert(key.to_string())
+            && let Some(Err(e)) = self.store.as_ref().map(|store| store.mark_read(key, self.now().with_timezone(&Utc)))
+        {
+            self.log(LogLevel::Warn, format!("Read state not saved: {}", e));
+        }
+    }
+
+    /// The item the user has moved to counts as read; called after each key or click.
+    /// Focus lent out by auto_focus_new isn't a choice the user made, so it doesn't count.
+    fn mark_selected_read(&mut self) {
+        if self.auto_focus_until.is_some() {
+            return;
+        }
+        if let Some(key) = self.focused_item().map(|(_, item)| item.key().to_string()) {
+            self.mark_read(&key);
+        }
+    }
+
+    fn open_reader(&mut self) {
+        let Some((feed_idx, item)) = self.focused_item().map(|(idx, item)| (idx, item.clone())) else { return };
+        self.open_item(feed_idx, item);
+    }
+
+    fn open_item(&mut self, feed_idx: usize, item: FeedItem) {
+        self.mark_read(item.key());
+        if let Some(url) = item.image.clone()
+            && self.image_picker.is_some()
+        {
+            self.fetch_image(feed_idx, url);
+        }
+        self.reader = Some(Reader { item, scroll: 0, max_scroll: Cell::new(0), image: None });
+    }
+
+    /// Downloads and decodes an image in the background, through the same proxy as its feed.
+    fn fetch_image(&self, feed_idx: usize, url: String) {
+        let client = self.clients.get(&self.config.proxy_for(&self.config.feeds[feed_idx].url));
+        let tx = self.image_tx.clone();
+        self.shutdown.spawn(async move {
+            let bytes = match client {
+                Ok(client) => rss_funcs::get_image(&client, &url).await.ok(),
+                Err(_) => None,
+            };
+            let image = match bytes {
+                Some(bytes) => tokio::task::spawn_blocking(move || image::load_from_memory(&bytes).ok()).await.ok().flatten(),
+                None => None,
+            };
+            let _ = tx.send((url, image));
+        });
+    }
+
+    fn show_image(&mut self, url: &str, image: Option<DynamicImage>) {
+        let (Some(reader), Some(picker), Some(image)) = (&mut self.reader, &self.image_picker, image) else { return };
+        // The reader may have moved on to another item while this one downloaded
+        if reader.item.image.as_deref() == Some(url) {
+            reader.image = Some(RefCell::new(picker.new_resize_protocol(image)));
+        }
+    }
+
+    /// Marks the focused feed's items read, or every feed's without focus; with a search active, only the matches.
+    fn mark_all_read(&mut self) {
+        let feeds: Vec<usize> = match self.focused_feed() {
+            Some(idx) => vec![idx],
+            None => (0..self.rss_feeds.len()).collect(),
+        };
+        let keys: Vec<String> = feeds
+            .into_iter()
+            .flat_map(|idx| self.visible_items(idx))
+            .map(|item| item.key().to_string())
+            .filter(|key| !self.read.contains(key))
+            .collect();
+        if let Some(Err(e)) = self.store.as_ref().map(|store| store.mark_all_read(&keys, self.now().with_timezone(&Utc))) {
+            self.log(LogLevel::Warn, format!("Read state not saved: {}", e));
+        }
+        let count = keys.len();
+        self.read.extend(keys);
+        self.show_notice(format!("marked {} read", count), false);
+    }
+
+    /// The item open in the reader, else the focused one; what y and e act on.
+    fn selected_item(&self) -> Option<&FeedItem> {
+        match &self.reader {
+            Some(reader) => Some(&reader.item),
+            None => self.focused_item().map(|(_, item)| item),
+        }
+    }
+
+    fn show_notice(&mut self, text: String, is_error: bool) {
+        self.log(if is_error { LogLevel::Error } else { LogLevel::Info }, text.clone());
+        self.notice = Some(Notice { text, is_error, until: Instant::now() + NOTICE_DURATION });
+    }
+
+    /// Adds to the log panel, and to the `--log-file` log if there is one.
+    fn log(&mut self, level: LogLevel, text: impl Into<String>) {
+        let text = text.into();
+        match level {
+            LogLevel::Info => tracing::info!("{}", text),
+            LogLevel::Warn => tracing::warn!("{}", text),
+            LogLevel::Error => tracing::error!("{}", text),
+        }
+        if self.log.len() == LOG_CAPACITY {
+            self.log.pop_front();
+        }
+        self.log.push_back(LogEntry { at: self.now(), level, text });
+    }
+
+    /// Copies the selected item's link and says so in the footer.
+    fn copy_link(&mut self) {
+        let Some(link) = self.selected_item().map(|item| item.link.clone()) else { return };
+        let result = if link.is_empty() { Err("this item has no link".to_string()) } else { self.set_clipboard(link) };
+        match result {
+            Ok(()) => self.show_notice("copied link".to_string(), false),
+            Err(e) => self.show_notice(format!("copy failed: {}", e), true),
+        }
+    }
+
+    /// Writes the selected item to `export_dir` as Markdown and shows where it went.
+    fn save_item(&mut self) {
+        let Some(item) = self.selected_item() else { return };
+        match export::save_markdown(item, &self.config.export_dir, self.config.timezone) {
+            Ok(path) => self.show_notice(format!("saved {}", path.display()), false),
+            Err(e) => self.show_notice(format!("save failed: {}", e), true),
+        }
+    }
+
+    fn set_clipboard(&mut self, text: String) -> Result<(), String> {
+        if self.clipboard.is_none() {
+            // No display server (SSH, plain console) means no clipboard
+            self.clipboard = Some(arboard::Clipboard::new().map_err(|e| e.to_string())?);
+        }
+        let clipboard = self.clipboard.as_mut().expect("just opened");
+        clipboard.set_text(text).map_err(|e| e.to_string())
+    }
+
+    /// The innermost recorded area under a cell of the last frame.
+    fn hit_at(&self, column: u16, row: u16) -> Option<Hit> {
+        let position = Position::new(column, row);
+        self.hits.borrow().iter().rev().find(|(area, _)| area.contains(position)).map(|(_, hit)| *hit)
+    }
+
+    /// Left click focuses the panel under the pointer (opening a feed item, or picking a list row and
+    /// opening it on a second click); the wheel scrolls whatever is under the pointer.
+    /// Overlays keep the mouse to themselves.
+    fn on_mouse(&mut self, event: MouseEvent) {
+        let wheel = match event.kind {
+            MouseEventKind::ScrollUp => Some(false),
+            MouseEventKind::ScrollDown => Some(true),
+            MouseEventKind::Down(MouseButton::Left) => None,
+            _ => return,
+        };
+        if self.reader.is_some() {
+            if let Some(down) = wheel {
+                self.scroll_reader(down);
+            }
+            return;
+        }
+        if self.help || self.digest.is_some() || self.palette.is_some() {
+            return;
+        }
+        let Some(hit) = self.hit_at(event.column, event.row) else { return };
+        match (hit, wheel) {
+            (Hit::Feed(_) | Hit::FeedItem(..), Some(down)) if self.view != ViewMode::Grid => self.scroll_zoom(down),
+            (Hit::Feed(idx) | Hit::FeedItem(idx, _), Some(down)) => {
+                self.focus_by_mouse(Panel::Feed(idx));
+                self.scroll_focused(down);
+            }
+            (Hit::Feed(idx), None) => self.focus_by_mouse(Panel::Feed(idx)),
+            (Hit::FeedItem(idx, item_idx), None) => {
+                self.focus_by_mouse(Panel::Feed(idx));
+                if let Some(item) = self.visible_items(idx).get(item_idx).map(|item| (*item).clone()) {
+                    self.open_item(idx, item);
+                }
+            }
+            (Hit::Telegram(panel_idx), wheel) => {
+                self.focus_by_mouse(Panel::Telegram(panel_idx));
+                if let Some(down) = wheel {
+                    self.scroll_telegram(panel_idx, down);
+                }
+            }
+            (Hit::ListRow(_), Some(down)) if self.view == ViewMode::Bookmarks => self.move_bookmark_selection(down),
+            (Hit::ListRow(_), Some(down)) => self.move_timeline_selection(down),
+            (Hit::ListRow(row), None) if self.view == ViewMode::Bookmarks => {
+                if row == self.bookmarks_selected {
+                    self.open_bookmark();
+                }
+                self.bookmarks_selected = row;
+            }
+            (Hit::ListRow(row), None) => {
+                if row == self.timeline_selected {
+                    self.open_reader();
+                }
+                self.timeline_selected = row;
+            }
+        }
+    }
+
+    fn focus_by_mouse(&mut self, panel: Panel) {
+        self.auto_focus_until = None;
+        self.focused = Some(panel);
+    }
+
+    fn scroll_reader(&mut self, down: bool) {
+        let Some(reader) = &mut self.reader else { return };
+        reader.scroll = if down {
+            (reader.scroll + 1).min(reader.max_scroll.get())
+        } else {
+            reader.scroll.saturating_sub(1)
+        };
+    }
+
+    /// Fills the window with one feed; it also takes focus so feed actions apply to it.
+    fn zoom(&mut self, idx: usize) {
+        if idx >= self.config.feeds.len() {
+            return;
+        }
+        self.focused = Some(Panel::Feed(idx));
+        self.auto_focus_until = None;
+        self.view = ViewMode::Focused(idx);
+        self.zoom_scroll = 0;
+    }
+
+    /// Pulls scroll positions and selections back inside what the last frame could show,
+    /// after a resize left them pointing past the end.
+    fn clamp_to_view(&mut self) {
+        for idx in 0..self.rss_feeds.len() {
+            let len = self.visible_items(idx).len();
+            let looping = self.config.rotation_for(&self.config.feeds[idx].url) == Rotation::Loop;
+            // Looping feeds wrap anyway; the rest stop with their last item at the bottom
+            if len > 0 && (!looping || self.focused_feed() == Some(idx)) {
+                self.offsets[idx] = (self.offsets[idx] % len).min(len.saturating_sub(self.shown_items(idx)));
+            }
+        }
+        self.zoom_scroll = self.zoom_scroll.min(self.zoom_max_scroll.get());
+        if let Some(reader) = &mut self.reader {
+            reader.scroll = reader.scroll.min(reader.max_scroll.get());
+        }
+        self.timeline_selected = self.timeline_selected.min(self.timeline().len().saturating_sub(1));
+        self.bookmarks_selected = self.bookmarks_selected.min(self.bookmarks.len().saturating_sub(1));
+    }
+
+    fn scroll_zoom(&mut self, down: bool) {
+        self.zoom_scroll = if down {
+            (self.zoom_scroll + 1).min(self.zoom_max_scroll.get())
+        } else {
+            self.zoom_scroll.saturating_sub(1)
+        };
+    }
+
+    fn toggle_digest(&mut self) {
+        self.digest = match self.digest {
+            Some(_) => None,
+            None => self.focused_feed(),
+        };
+    }
+
+    fn open_feed_homepage(&self) {
+        if let Some(feed) = self.focused_feed().and_then(|i| self.rss_feeds.get(i))
+            && !feed.link.is_empty()
+        {
+            let _ = open::that_detached(&feed.link);
+        }
+    }
+
+    fn apply_feed(&mut self, idx: usize, result: Result<Fetched, FetchError>) {
+        self.rate_limited_until[idx] = None;
+        self.retrying.remove(&idx);
+        let url = self.config.feeds[idx].url.clone();
+        let cache_hours = self.config.offline_cache_hours;
+        let mut cached_at = None;
+        let title = self.config.feeds[idx].title.trim().to_string();
+        // A failed fetch counts as an empty result for the empty policy
+        let mut feed = match result {
+            Err(FetchError::RateLimited(wait)) => {
+                // What's on screen stays; retry_rate_limited fetches again once the wait is over
+                // Capped at a day so an absurd header can't park the feed forever
+                let wait = wait.unwrap_or(RATE_LIMIT_FALLBACK_WAIT).min(Duration::from_secs(24 * 60 * 60));
+                let wait = chrono::Duration::from_std(wait).expect("capped at a day");
+                self.rate_limited_until[idx] = Some(self.now() + wait);
+                self.log(LogLevel::Warn, format!("{}: rate limited, retrying in {}s", title, wait.num_seconds()));
+                self.fetch_state[idx] = FetchState::Idle;
+                self.errors[idx] = None;
+                return;
+            }
+            Ok(Fetched::NotModified) => {
+                // What's on screen is current; nothing arrived since the last refresh
+                self.fetch_state[idx] = FetchState::Loaded;
+                self.errors[idx] = None;
+                self.offline[idx] = false;
+                self.stale[idx] = false;
+                self.new_items[idx].clear();
+                self.fetched_at[idx] = Some(self.now());
+                self.log(LogLevel::Info, format!("{}: not modified", title));
+                // The offline copy is still the current one, so its age starts over
+                if cache_hours > 0
+                    && let Some(Err(e)) = self.cache.as_ref().map(|cache| cache.touch(&url, self.now().with_timezone(&Utc)))
+                {
+                    self.log(LogLevel::Warn, format!("{}: offline copy not updated: {}", title, e));
+                }
+                return;
+            }
+            Ok(Fetched::Modified(feed, validators)) => {
+                self.fetch_state[idx] = FetchState::Loaded;
+                self.errors[idx] = None;
+                self.validators.insert(url.clone(), validators);
+                self.log(LogLevel::Info, format!("{}: {} items", title, feed.items.len()));
+                if cache_hours > 0
+                    && let Some(Err(e)) = self.cache.as_ref().map(|cache| cache.save(&url, &feed, self.now().with_timezone(&Utc)))
+                {
+                    self.log(LogLevel::Warn, format!("{}: offline copy not saved: {}", title, e));
+                }
+                feed
+            }
+            Err(e) => {
+                self.fetch_state[idx] = FetchState::Error;
+                self.errors[idx] = Some(e.to_string());
+                self.log(LogLevel::Error, format!("{}: {}", title, e));
+                // Unreachable host: the last good copy beats an empty block, unless it's too old to trust
+                let max_age = chrono::Duration::hours(cache_hours as i64);
+                let now = self.now().with_timezone(&Utc);
+                let cached = match e {
+                    FetchError::Network(_) if cache_hours > 0 => self.cache.as_ref().and_then(|cache| cache.load(&url, max_age, now)),
+                    _ => None,
+                };
+                match cached {
+                    Some((feed, at)) => {
+                        let at = at.with_timezone(&Local);
+                        self.log(LogLevel::Warn, format!("{}: showing offline copy from {}", title, at.format("%H:%M")));
+                        cached_at = Some(at);
+                        feed
+                    }
+                    None => Feed::default(),
+                }
+            }
+        };
+        self.offline[idx] = cached_at.is_some();
+        // Duplicate entries within one response collapse to the first
+        let mut unique = HashSet::new();
+        feed.items.retain(|item| unique.insert(item.key().to_string()));
+
+        let previous = self.response_len[idx];
+        let shrunk = previous >= SHRINK_MIN_ITEMS && feed.items.len() < previous / 2;
+        // Measured against this one, a second shrunken refresh in a row is taken as the feed's new size
+        if !feed.items.is_empty() {
+            self.response_len[idx] = feed.items.len();
+        }
+        let keep_old = (feed.items.is_empty() || shrunk)
+            && !self.rss_feeds[idx].items.is_empty()
+            && self.config.empty_policy == EmptyPolicy::KeepOnEmpty;
+        self.stale[idx] = keep_old;
+        if keep_old {
+            if !feed.items.is_empty() {
+                self.log(LogLevel::Warn, format!("{}: only {} items, down from {}; keeping the last ones", title, feed.items.len(), previous));
+            }
+            return;
+        }
+        // The first load isn't "new"; after that, any unseen item is
+        let first_load = self.seen[idx].is_empty();
+        self.new_items[idx] = if first_load {
+            HashSet::new()
+        } else {
+            unique.difference(&self.seen[idx]).cloned().collect()
+        };
+        let newest_new = feed.items.iter().position(|item| self.new_items[idx].contains(item.key()));
+        if self.config.desktop_notifications && self.config.feeds[idx].notify && !self.is_quiet() {
+            let headlines: Vec<String> = feed
+                .items
+                .iter()
+                .filter(|item| self.new_items[idx].contains(item.key()))
+                .map(|item| item.title.clone())
+                .collect();
+            if !headlines.is_empty() {
+                notify::new_items(&self.config.feeds[idx].title, headlines);
+            }
+        }
+        // Only what the feed still lists is remembered, so a long session doesn't pile up old keys
+        self.seen[idx] = unique;
+        // The first items since startup pick up where the last run left off
+        let saved_offset = self.ui_state.offsets.get(&self.config.feeds[idx].url).copied().filter(|_| first_load);
+        self.offsets[idx] = saved_offset.map_or(0, |offset| offset.min(feed.items.len().saturating_sub(1)));
+        self.rss_feeds[idx] = feed;
+        self.fetched_at[idx] = Some(cached_at.unwrap_or_else(|| self.now()));
+        if let Some(pos) = newest_new {
+            self.auto_focus(idx, pos);
+        }
+    }
+
+    /// Focus, view and selection from the last run, for feeds that are still configured.
+    fn restore_ui_state(&mut self) {
+        let feed_index = |url: &str| self.config.feeds.iter().position(|feed| feed.url == url);
+        self.focused = self.ui_state.focused_feed.as_deref().and_then(feed_index).map(Panel::Feed);
+        self.view = match &self.ui_state.view {
+            SavedView::Grid => ViewMode::Grid,
+            SavedView::Focused(url) => feed_index(url).map_or(ViewMode::Grid, ViewMode::Focused),
+            SavedView::Timeline => ViewMode::Timeline,
+            SavedView::Bookmarks => ViewMode::Bookmarks,
+        };
+        self.timeline_selected = self.ui_state.timeline_selected;
+    }
+
+    /// Writes the current place in every feed, plus focus and view, for the next run.
+    fn save_ui_state(&mut self) -> anyhow::Result<()> {
+        let feeds = &self.config.feeds;
+        let state = &mut self.ui_state;
+        // Feeds that never loaded this run keep their old position; removed feeds are forgotten
+        state.offsets.retain(|url, _| feeds.iter().any(|feed| feed.url == *url));
+        for (idx, feed) in feeds.iter().enumerate() {
+            let len = self.rss_feeds[idx].items.len();
+            if len > 0 {
+                state.offsets.insert(feed.url.clone(), self.offsets[idx] % len);
+            }
+        }
+        // Focus borrowed by auto_focus_new isn't the user's
+        state.focused_feed = match self.focused {
+            Some(Panel::Feed(idx)) if self.auto_focus_until.is_none() => Some(feeds[idx].url.clone()),
+            _ => None,
+        };
+        state.view = match self.view {
+            ViewMode::Grid => SavedView::Grid,
+            ViewMode::Focused(idx) => SavedView::Focused(feeds[idx].url.clone()),
+            ViewMode::Timeline => SavedView::Timeline,
+            ViewMode::Bookmarks => SavedView::Bookmarks,
+        };
+        state.timeline_selected = self.timeline_selected;
+        state.save()
+    }
+
+    fn auto_focus(&mut self, feed_idx: usize, item_pos: usize) {
+        // Never steal focus the user chose
+        if !self.config.auto_focus_new || (self.focused.is_some() && self.auto_focus_until.is_none()) {
+            return;
+        }
+        self.focused = Some(Panel::Feed(feed_idx));
+        self.offsets[feed_idx] = item_pos;
+        self.auto_focus_until = Some(self.now() + chrono::Duration::seconds(self.config.auto_focus_secs as i64));
+    }
+
+    /// Fetches each rate-limited feed again as soon as its Retry-After has passed.
+    fn retry_rate_limited(&mut self) {
+        let now = self.now();
+        for idx in 0..self.rate_limited_until.len() {
+            if self.rate_limited_until[idx].is_some_and(|until| now >= until) {
+                self.rate_limited_until[idx] = None;
+                self.fetch_feed(idx);
+            }
+        }
+    }
+
+    fn expire_auto_focus(&mut self) {
+        if self.auto_focus_until.is_some_and(|until| self.now() >= until) {
+            self.auto_focus_until = None;
+            self.focused = None;
+        }
+    }
+
+    fn on_focus_gained(&mut self) {
+        self.window_focused = true;
+        if self.last_focus_refresh.is_some_and(|at| at.elapsed() < FOCUS_REFRESH_DEBOUNCE) {
+            return;
+        }
+        self.last_focus_refresh = Some(Instant::now());
+        self.fetch_rss();
+    }
+
+    fn on_tick(&mut self) {
+        // Feeds still refresh while the decoration is frozen
+        if self.animation_frozen() {
+            return;
+        }
+        for (idx, feed) in self.config.feeds.iter().enumerate() {
+            if self.focused_feed() == Some(idx) {
+                continue;
+            }
+            let len = self.rss_feeds[idx].items.len();
+            let shown = self.shown_items(idx);
+            let offset = &mut self.offsets[idx];
+            match self.config.rotation_for(&feed.url) {
+                Rotation::Loop => *offset = offset.wrapping_add(1),
+                // Stop once the oldest item is on screen
+                Rotation::Once => *offset = (*offset + 1).min(len.saturating_sub(shown)),
+                Rotation::Off => {}
+            }
+        }
+    }
+
+    /// Rotation and the spinner hold still while paused, during quiet hours or while in the background.
+    fn animation_frozen(&self) -> bool {
+        self.paused || self.is_quiet() || !self.window_focused
+    }
+
+    fn spinner(&self) -> &'static str {
+        if self.animation_frozen() {
+            return SPINNER[0];
+        }
+        let frame = self.spinner_started.elapsed().as_millis() / SPINNER_FRAME.as_millis();
+        SPINNER[frame as usize % SPINNER.len()]
+    }
+
+    fn is_quiet(&self) -> bool {
+        self.config
+            .quiet_hours
+            .is_some_and(|q| q.contains(self.now().time()))
+    }
+
+    /// Fetches every feed concurrently; each result is sent as soon as it lands so
+    /// blocks fill in progressively and a slow host only holds up its own block.
+    fn fetch_rss(&mut self) {
+        self.last_refresh = Instant::now();
+        for idx in 0..self.config.feeds.len() {
+            self.fetch_feed(idx);
+        }
+    }
+
+    /// Fetches again only the feeds that errored or came back empty, so after an outage the
+    /// working ones keep their items and place. A rate-limited feed is idle, so it waits its turn.
+    fn retry_failed(&mut self) {
+        let failed: Vec<usize> = (0..self.config.feeds.len())
+            .filter(|&idx| match self.fetch_state[idx] {
+                FetchState::Error => true,
+                FetchState::Loaded => self.rss_feeds[idx].items.is_empty(),
+                FetchState::Idle | FetchState::Loading => false,
+            })
+            .collect();
+        if failed.is_empty() {
+            self.show_notice("no failed feeds to retry".to_string(), false);
+            return;
+        }
+        for &idx in &failed {
+            self.fetch_feed(idx);
+        }
+        self.retrying.extend(failed);
+    }
+
+    /// Re-fetches one feed in the background; the other blocks and the refresh countdown are left alone.
+    fn fetch_feed(&mut self, idx: usize) {
+        // A rate-limited feed waits out its Retry-After, whatever asked for the refresh
+        if self.rate_limited_until[idx].is_some_and(|until| self.now() < until) {
+            return;
+        }
+        self.fetch_state[idx] = FetchState::Loading;
+        let tx = self.tx.clone();
+        let url = self.config.feeds[idx].url.clone();
+        let client = self.clients.get(&self.config.proxy_for(&url));
+        // A 304 only helps if there are items to keep
+        let validators = if self.rss_feeds[idx].items.is_empty() {
+            Validators::default()
+        } else {
+            self.validators.get(&url).cloned().unwrap_or_default()
+        };
+        self.shutdown.spawn(async move {
+            let result = match client {
+                Ok(client) => rss_funcs::get_feed_with_retry(&client, &url, &validators).await,
+                Err(e) => Err(FetchError::Proxy(e.to_string())),
+            };
+            let _ = tx.send((idx, result));
+        });
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    dotenvy::dotenv().ok();
+    logging::init()?;
+    let mut config = Config::load()?;
+    if let Some(secs) = config::arg_value("--interval") {
+        config.refresh_interval_secs = secs.parse().map_err(|_| anyhow::anyhow!("--interval expects seconds, got {:?}", secs))?;
+    }
+    let ui_state = UiState::load();
+    if let Some(name) = config::arg_value("--theme") {
+        config.theme = name;
+    } else if let Some(name) = &ui_state.theme
+        && Theme::load(name).is_ok()
+    {
+        // The theme last picked with [C], unless it has since disappeared
+        config.theme = name.clone();
+    }
+    if let Some(path) = config::arg_value("--import-opml") {
+        config.feeds = opml::import(Path::new(&path), config.feed_columns(), config.slots_per_column)?;
+    }
+    match config::subcommand().as_deref() {
+        Some("check") => {
+            let all_ok = headless::check(&config, config::has_flag("--json")).await?;
+            std::process::exit(if all_ok { 0 } else { 1 });
+        }
+        Some(other) => anyhow::bail!("unknown command {:?}; the only one is \"check\"", other),
+        None => {}
+    }
+    if config::has_flag("--dump-json") {
+        return headless::dump_json(&config).await;
+    }
+    if let Some(path) = config::arg_value("--export-opml") {
+        opml::export(&config.feeds, Path::new(&path))?;
+        println!("Wrote {} feeds to {}", config.feeds.len(), path);
+        return Ok(());
+    }
+    
+    // Signing out is asked for explicitly, so --no-telegram (which only skips the monitor) doesn't stop it
+    if config::has_flag("--logout") {
+        let Some((api_id, _)) = telegram_credentials()? else {
+            anyhow::bail!("--logout needs TG_API_ID and TG_API_HASH to reach Telegram");
+        };
+        return TelegramMonitor::new().logout(api_id).await;
+    }
+    // Without credentials this is a plain RSS reader; tg_rx simply never receives anything
+    let telegram_disabled = config::has_flag("--no-telegram");
+    let credentials = if telegram_disabled { None } else { telegram_credentials()? };
+    let mut target_ids: Vec<i64> = env::var("TG_CHAT_IDS")
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|s| s.trim().parse().ok())
+        .collect();
+    // Chats named only by a panel still need monitoring
+    for panel in &config.telegram_panels {
+        for id in &panel.chat_ids {
+            if !target_ids.contains(id) {
+                target_ids.push(*id);
+            }
+        }
+    }
+
+    let (tx, rx) = mpsc::unbounded_channel();
+    let (tg_tx, tg_rx) = mpsc::unbounded_channel();
+    let (reauth_tx, mut reauth_rx) = mpsc::unbounded_channel();
+    let (status_tx, mut status_rx) = mpsc::unbounded_channel();
+    let shutdown = Shutdown::default();
+
+    // Don't block on an interactive login while offline; it can be retried from the TUI
+    let mut telegram_online = false;
+    if let Some((api_id, api_hash)) = &credentials {
+        telegram_online = !config.telegram_preflight || telegram_funcs::is_reachable().await;
+        if telegram_online {
+            start_telegram(*api_id, api_hash, &target_ids, tg_tx.clone(), reauth_tx.clone(), status_tx.clone(), config.telegram_max_restarts, &shutdown).await?;
+        }
+    }
+
+    let theme = Theme::load(&config.theme)?.with_level(ColorLevel::detect()?);
+    let mut app = App::new(tx, rx, tg_rx, shutdown.clone(), ui_state, config, theme);
+    app.restore_ui_state();
+    app.telegram_configured = credentials.is_some();
+    app.telegram_disabled = telegram_disabled;
+    app.telegram_offline = app.telegram_configured && !telegram_online;
+    app.fetch_rss();
+
+    enter_tui(&app.config)?;
+    // Asks the terminal about graphics support, so it must run before the event loop reads stdin.
+    // Halfblocks would only be a smudge at popup size; the reader shows the URL instead.
+    if app.config.inline_images {
+        app.image_picker = Picker::from_query_stdio().ok().filter(|p| p.protocol_type() != ProtocolType::Halfblocks);
+    }
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+
+    let rotate_interval = app.config.rotate_interval();
+    let refresh_interval = app.config.refresh_interval();
+    let mut last_tick = Instant::now();
+
+    loop {
+        while let Ok((idx, result)) = app.rx.try_recv() {
+            app.apply_feed(idx, result);
+        }
+        app.expire_auto_focus();
+        app.retry_rate_limited();
+        while let Ok((idx, page)) = app.page_rx.try_recv() {
+            app.append_page(idx, page);
+        }
+        while let Ok((url, image)) = app.image_rx.try_recv() {
+            app.show_image(&url, image);
+        }
+        while let Ok(state) = status_rx.try_recv() {
+            let (level, text) = match &state {
+                ConnectionState::Connected => (LogLevel::Info, "connected".to_string()),
+                ConnectionState::Reconnecting { attempt, error } => (LogLevel::Warn, format!("reconnecting (attempt {}): {}", attempt, error)),
+                ConnectionState::Restarting { restart, max, error } => (LogLevel::Warn, format!("restarting ({}/{}): {}", restart, max, error)),
+                ConnectionState::Failed(err) => (LogLevel::Error, format!("stopped: {}", err)),
+            };
+            app.log(level, format!("Telegram {}", text));
+            // A monitor out of restarts can only be brought back by [T]
+            if let ConnectionState::Failed(err) = &state {
+                app.telegram_error = Some(err.clone());
+                app.telegram_offline = true;
+            }
+            app.telegram_status = Some(state);
+        }
+        while let Ok(msg) = app.tg_rx.try_recv() {
+            app.log_telegram(&msg);
+            app.push_telegram(msg);
+        }
+        if let Ok(ReauthRequest { client, done }) = reauth_rx.try_recv()
+            && let Some((_, api_hash)) = &credentials
+        {
+            // Same dance as the [T] reconnect: prompts go to the normal screen
+            leave_tui()?;
+            println!("Telegram session expired; please log in again.");
+            let result = TelegramMonitor::new().ensure_authorized(&client, api_hash).await;
+            enter_tui(&app.config)?;
+            terminal.clear()?;
+            app.telegram_error = result.as_ref().err().map(|e| e.to_string());
+            match &app.telegram_error {
+                Some(e) => app.log(LogLevel::Error, format!("Telegram login failed: {}", e)),
+                None => app.log(LogLevel::Info, "Telegram logged in again"),
+            }
+            let _ = done.send(result.is_ok());
+        }
+
+        app.mark_telegram_read();
+        app.hits.borrow_mut().clear();
+        // Only a full frame measures what fits; until one is drawn a resize stays pending
+        let mut laid_out = false;
+        terminal.draw(|frame| {
+            let area = frame.area();
+            frame.render_widget(Block::default().bg(app.theme.background).fg(app.theme.text), area);
+            if area.width < MIN_COLUMNS || area.height < MIN_ROWS {
+                render_too_small(frame, area, &app.theme);
+                return;
+            }
+            laid_out = true;
+
+            let time_left = refresh_interval.as_secs_f32() - app.last_refresh.elapsed().as_secs_f32();
+            let footer_lines = fit_footer(footer_segments(&app, time_left), area.width as usize, app.config.footer_overflow);
+
+            let main_layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(10),
+                    Constraint::Length(if app.show_log { LOG_PANEL_ROWS } else { 0 }),
+                    Constraint::Length(footer_lines.len() as u16),
+                ])
+                .split(area);
+
+            match app.view {
+                ViewMode::Grid => render_grid(frame, main_layout[0], &app),
+                ViewMode::Focused(idx) => render_focused_feed(frame, main_layout[0], &app, idx),
+                ViewMode::Timeline => render_timeline(frame, main_layout[0], &app),
+                ViewMode::Bookmarks => render_bookmarks(frame, main_layout[0], &app),
+            }
+
+            if app.show_log {
+                render_log(frame, main_layout[1], &app);
+            }
+
+            // --- Footer ---
+            frame.render_widget(Paragraph::new(footer_lines), main_layout[2]);
+
+            if let Some(idx) = app.digest {
+                render_digest(frame, area, &app, idx);
+            }
+            if let Some(reader) = &app.reader {
+                render_reader(frame, area, reader, app.config.timezone, &app.theme);
+            }
+            if app.help {
+                render_help(frame, area, &app.config.keymap, &app.theme);
+            }
+            if let Some(palette) = &app.palette {
+                render_palette(frame, area, palette, &app.config.keymap, &app.theme);
+            }
+        })?;
+        if laid_out && std::mem::take(&mut app.resized) {
+            app.clamp_to_view();
+        }
+
+        if event::poll(Duration::from_millis(100))? {
+            match event::read()? {
+                event::Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    let action = if app.telegram_query_editing {
+                        app.telegram_query_key(key.code);
+                        None
+                    } else if app.feed_query_editing {
+                        app.feed_query_key(key.code);
+                        None
+                    } else if app.palette.is_some() {
+                        app.palette_key(key.code)
+                    } else if let Some(action) = app.config.keymap.action_for(key) {
+                        Some(action)
+                    } else if let KeyCode::Char(digit @ '1'..='9') = key.code {
+                        // Feeds are numbered in layout order, down each column
+                        app.zoom(digit as usize - '1' as usize);
+                        None
+                    } else {
+                        None
+                    };
+                    match action {
+                        Some(Action::Quit) => break,
+                        Some(Action::ConnectTelegram) if app.telegram_offline && let Some((api_id, api_hash)) = &credentials => {
+                            if telegram_funcs::is_reachable().await {
+                                // The login prompts need the normal screen
+                                leave_tui()?;
+                                let result = start_telegram(*api_id, api_hash, &target_ids, tg_tx.clone(), reauth_tx.clone(), status_tx.clone(), app.config.telegram_max_restarts, &shutdown).await;
+                                enter_tui(&app.config)?;
+                                terminal.clear()?;
+                                app.telegram_error = result.err().map(|e| e.to_string());
+                                app.telegram_offline = app.telegram_error.is_some();
+                                match &app.telegram_error {
+                                    Some(e) => app.log(LogLevel::Error, format!("Telegram connect failed: {}", e)),
+                                    None => app.log(LogLevel::Info, "Telegram started"),
+                                }
+                            } else {
+                                app.telegram_error = Some("Still unreachable".to_string());
+                                app.log(LogLevel::Warn, "Telegram still unreachable");
+                            }
+                        }
+                        Some(action) => app.perform(action),
+                        None => {}
+                    }
+                    app.mark_selected_read();
+                }
+                event::Event::Mouse(mouse) => {
+                    app.on_mouse(mouse);
+                    app.mark_selected_read();
+                }
+                event::Event::Resize(..) => app.resized = true,
+                event::Event::FocusGained => app.on_focus_gained(),
+                event::Event::FocusLost => app.window_focused = false,
+                _ => {}
+            }
+        }
+
+        if last_tick.elapsed() >= rotate_interval {
+            app.on_tick();
+            last_tick = Instant::now();
+        }
+        if app.last_refresh.elapsed() >= refresh_interval {
+            app.fetch_rss();
+        }
+    }
+
+    leave_tui()?;
+    if let Err(e) = app.save_ui_state() {
+        eprintln!("warning: couldn't save UI state: {}", e);
+    }
+    shutdown.finish().await;
+    Ok(())
+}
+
+fn enter_tui(config: &Config) -> anyhow::Result<()> {
+    enable_raw_mode()?;
+    stdout().execute(EnterAlternateScreen)?;
+    if config.refresh_on_focus {
+        stdout().execute(EnableFocusChange)?;
+    }
+    if config.mouse {
+        stdout().execute(EnableMouseCapture)?;
+    }
+    Ok(())
+}
+
+fn leave_tui() -> anyhow::Result<()> {
+    stdout().execute(DisableFocusChange)?;
+    stdout().execute(DisableMouseCapture)?;
+    disable_raw_mode()?;
+    stdout().execute(LeaveAlternateScreen)?;
+    Ok(())
+}
+
+/// `TG_API_ID` and `TG_API_HASH`, or `None` when either is unset and Telegram should be skipped.
+fn telegram_credentials() -> anyhow::Result<Option<(i32, String)>> {
+    let var = |name| env::var(name).ok().filter(|v: &String| !v.trim().is_empty());
+    let (Some(api_id), Some(api_hash)) = (var("TG_API_ID"), var("TG_API_HASH")) else {
+        return Ok(None);
+    };
+    let api_id = api_id.trim().parse().map_err(|_| anyhow::anyhow!("TG_API_ID must be a number, got {:?}", api_id))?;
+    Ok(Some((api_id, api_hash.trim().to_string())))
+}
+
+/// Logs in (prompting on stdin if needed) and spawns the monitor feeding `ui_tx`.
+#[allow(clippy::too_many_arguments)]
+async fn start_telegram(
+    api_id: i32,
+    api_hash: &str,
+    target_ids: &[i64],
+    ui_tx: mpsc::UnboundedSender<TelegramMessage>,
+    reauth_tx: mpsc::UnboundedSender<ReauthRequest>,
+    status_tx: mpsc::UnboundedSender<ConnectionState>,
+    max_restarts: u32,
+    shutdown: &Shutdown,
+) -> anyhow::Result<()> {
+    let monitor = TelegramMonitor::new();
+    let (tg_client, updates) = monitor.create_client(api_id, &shutdown.tasks).await?;
+    monitor.ensure_authorized(&tg_client, api_hash).await?;
+
+    let target_ids = target_ids.to_vec();
+    let token = shutdown.token.clone();
+    // Not `Shutdown::spawn`: the supervisor disconnects the client itself when cancelled
+    shutdown.tasks.spawn(async move {
+        monitor.supervise(tg_client, updates, target_ids, ui_tx, reauth_tx, status_tx, max_restarts, token).await;
+    });
+    Ok(())
+}
+
+/// Feed columns, then the Telegram column unless it's hidden.
+fn render_grid(frame: &mut Frame, area: Rect, app: &App) {
+    // Hiding Telegram hands its share back to the RSS columns, keeping their proportions
+    let feed_columns = app.config.feed_columns();
+    let mut column_widths: Vec<Constraint> = if app.telegram_visible() {
+        app.config.column_widths.iter().map(|&w| Constraint::Percentage(w)).collect()
+    } else {
+        app.config.column_widths.iter().map(|&w| Constraint::Fill(w)).collect()
+    };
+    if app.telegram_visible() {
+        column_widths.push(Constraint::Percentage(app.config.telegram_width()));
+    }
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(column_widths)
+        .split(area);
+
+    // --- Feed columns: RSS blocks, placed by their configured column and slot ---
+    // Empty blocks still take their slot (showing why they're empty); only Telegram's column ever folds away
+    let slot_count = app.config.slots_per_column;
+    for (column, &column_area) in columns.iter().take(feed_columns).enumerate() {
+        let slots = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Ratio(1, slot_count as u32); slot_count])
+            .split(column_area);
+        for (idx, feed) in app.config.feeds.iter().enumerate().filter(|(_, f)| f.column == column) {
+            let color = app.feed_color(idx);
+            let tag = feed.category.as_deref().map(|tag| (tag, color));
+            // Without a fixed count the block takes as many items as its height allows
+            let count = app.items_per_block.unwrap_or(usize::MAX);
+            render_rss_block(frame, slots[feed.slot], app, idx, &feed.title, color, count, tag);
+        }
+    }
+
+    // --- Last column: Telegram (one stacked panel per configured chat group) ---
+    if app.telegram_visible() {
+        let panels = app.config.telegram_panels();
+        let panel_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Ratio(1, panels.len() as u32); panels.len()])
+            .split(columns[feed_columns]);
+        for (i, (panel, &sub_area)) in panels.iter().zip(panel_layout.iter()).enumerate() {
+            // Connection notices only need saying once, in the top panel
+            render_telegram_panel(frame, sub_area, app, i, panel);
+        }
+    }
+}
+
+/// One feed across the whole window: every loaded item with its whole summary, scrolled by line.
+fn render_focused_feed(frame: &mut Frame, area: Rect, app: &App, feed_idx: usize) {
+    let theme = &app.theme;
+    let feed = &app.config.feeds[feed_idx];
+    let color = theme.adapt(app.feed_color(feed_idx));
+    let width = (area.width as usize).saturating_sub(2);
+    let query = app.feed_query.to_lowercase();
+
+    let mut lines: Vec<Line> = Vec::new();
+    for item in app.visible_items(feed_idx) {
+        let title_color = if app.read.contains(item.key()) { theme.muted } else { theme.text };
+        let mut header = vec![Span::styled("◆ ", Style::default().fg(color))];
+        if app.new_items[feed_idx].contains(item.key()) {
+            header.push(Span::styled("NEW ", Style::default().fg(color).bold()));
+        }
+        header.extend(highlight_matches(&item.title, &query, Style::default().bold().fg(title_color), theme));
+        header.push(Span::styled(format!("  {}", item.full_date(app.config.timezone)), Style::default().fg(theme.muted).italic()));
+        lines.push(Line::from(header));
+        for row in wrap_text(&item.summary, width) {
+            lines.push(Line::from(highlight_matches(&row, &query, Style::default().fg(theme.muted), theme)));
+        }
+        lines.push(Line::from(Span::styled("─".repeat(width), Style::default().fg(theme.border))));
+    }
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled("   Nothing to show yet", Style::default().fg(theme.muted).italic())));
     }
 
-    fn on_tick(&mut self) {
-        self.offset = self.offset.wrapping_add(1);
-    }
+    let visible = area.height.saturating_sub(2) as usize;
+    app.zoom_max_scroll.set(lines.len().saturating_sub(visible) as u16);
+    let scroll = app.zoom_scroll.min(app.zoom_max_scroll.get());
 
-    fn fetch_rss(&self) {
-        let tx = self.tx.clone();
-        let urls = vec![
-            // Left Column (Tech)
-            "https://feeds.feedburner.com/TheHackersNews",
-            "https://www.computerweekly.com/rss/Latest-IT-news.xml",
-            "https://sdtimes.com/feed/",
-            // Middle Column (News)
-            "https://www.investing.com/rss/news_25.rss",
-            "https://www.channelnewsasia.com/api/v1/rss-outbound-feed?_format=xml",
-            "https://www.channelnewsasia.com/api/v1/rss-outbound-feed?_format=xml&category=10416",
-        ];
+    let block = create_block(format!("{}[Esc] back ", feed.title), color, theme).border_style(Style::default().fg(color));
+    frame.render_widget(Paragraph::new(lines).scroll((scroll, 0)).block(block), area);
+    app.hits.borrow_mut().push((area, Hit::Feed(feed_idx)));
+}
 
-        tokio::spawn(async move {
-            let mut categorized_feeds = Vec::new();
-            for url in urls {
-                if let Ok(feeds) = rss_funcs::get_feed(url).await {
-                    categorized_feeds.push(feeds);
-                } else {
-                    categorized_feeds.push(vec![]); 
-                }
+/// Every feed's items as one list, newest first, each led by its feed's name in the feed's colour.
+fn render_timeline(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let query = app.feed_query.to_lowercase();
+    let entries = app.timeline();
+    let name_width = entries.iter().map(|(idx, _)| app.config.feeds[*idx].title.trim().width()).max().unwrap_or(0);
+
+    let mut rows: Vec<ListItem> = entries
+        .iter()
+        .map(|&(feed_idx, item)| {
+            let color = theme.adapt(app.feed_color(feed_idx));
+            let name = app.config.feeds[feed_idx].title.trim();
+            let title_color = if app.read.contains(item.key()) { theme.muted } else { theme.text };
+            let mut spans = vec![
+                Span::styled(format!(" {}{} ", name, " ".repeat(name_width - name.width())), Style::default().fg(color).bold()),
+                Span::styled(format!("{}  ", item.date_time(app.config.timezone)), Style::default().fg(theme.muted).italic()),
+            ];
+            if app.new_items[feed_idx].contains(item.key()) {
+                spans.push(Span::styled("NEW ", Style::default().fg(color).bold()));
             }
-            let _ = tx.send(categorized_feeds);
-        });
+            spans.extend(highlight_matches(&item.title, &query, Style::default().fg(title_color), theme));
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    if rows.is_empty() {
+        rows.push(ListItem::new(Span::styled("   Nothing to show yet", Style::default().fg(theme.muted).italic())));
     }
+
+    let mut state = ListState::default().with_selected(Some(app.timeline_selected.min(entries.len().saturating_sub(1))));
+    let block = create_block(format!(" TIMELINE · {} items [Esc] back ", entries.len()), theme.chrome, theme);
+    frame.render_stateful_widget(List::new(rows).block(block).highlight_style(Style::default().bg(theme.border)), area, &mut state);
+    record_list_rows(app, area, state.offset(), entries.len());
 }
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    dotenvy::dotenv().ok();
-    
-    let api_id = env::var("TG_API_ID")?.parse::<i32>()?;
-    let api_hash = env::var("TG_API_HASH")?;
-    let target_ids: Vec<i64> = env::var("TG_CHAT_IDS")
-        .unwrap_or_default()
-        .split(',')
-        .filter_map(|s| s.trim().parse().ok())
+/// One hit area per row a bordered one-line-per-row list showed, starting at `offset`.
+fn record_list_rows(app: &App, area: Rect, offset: usize, len: usize) {
+    let inner = area.inner(Margin::new(1, 1));
+    let mut hits = app.hits.borrow_mut();
+    for (i, row) in (offset..len).take(inner.height as usize).enumerate() {
+        hits.push((Rect::new(inner.x, inner.y + i as u16, inner.width, 1), Hit::ListRow(row)));
+    }
+}
+
+/// Starred items with their feed and date; the feed name falls back to the URL for removed feeds.
+fn render_bookmarks(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let source = |b: &Bookmark| {
+        app.config.feeds.iter().position(|feed| feed.url == b.feed_url).map_or_else(
+            || (b.feed_url.clone(), theme.muted),
+            |idx| (app.config.feeds[idx].title.trim().to_string(), theme.adapt(app.feed_color(idx))),
+        )
+    };
+    let sources: Vec<(String, Color)> = app.bookmarks.iter().map(source).collect();
+    let name_width = sources.iter().map(|(name, _)| name.width()).max().unwrap_or(0);
+
+    let mut rows: Vec<ListItem> = app
+        .bookmarks
+        .iter()
+        .zip(&sources)
+        .map(|(b, (name, color))| {
+            let date = rss_funcs::format_date(b.published, app.config.timezone, "%b %d %H:%M");
+            let title_color = if app.read.contains(&b.key) { theme.muted } else { theme.text };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!(" {}{} ", name, " ".repeat(name_width - name.width())), Style::default().fg(*color).bold()),
+                Span::styled(format!("{}  ", date), Style::default().fg(theme.muted).italic()),
+                Span::styled(b.title.trim().to_string(), Style::default().fg(title_color)),
+            ]))
+        })
         .collect();
+    if rows.is_empty() {
+        rows.push(ListItem::new(Span::styled("   Nothing starred yet; [b] stars the focused item", Style::default().fg(theme.muted).italic())));
+    }
 
-    let monitor = TelegramMonitor::new();
-    let tg_client = monitor.create_client(api_id).await
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
-    monitor.ensure_authorized(&tg_client, &api_hash).await?;
+    let selected = app.bookmarks_selected.min(app.bookmarks.len().saturating_sub(1));
+    let mut state = ListState::default().with_selected(Some(selected));
+    let title = format!(" STARRED · {} items [Enter] open [b] unstar [Esc] back ", app.bookmarks.len());
+    let block = create_block(title, theme.chrome, theme);
+    frame.render_stateful_widget(List::new(rows).block(block).highlight_style(Style::default().bg(theme.border)), area, &mut state);
+    record_list_rows(app, area, state.offset(), app.bookmarks.len());
+}
 
-    let (tx, rx) = mpsc::unbounded_channel();
-    let (tg_tx, tg_rx) = mpsc::unbounded_channel();
+/// A piece of the footer; lower `priority` values survive longest on narrow terminals.
+struct FooterSegment {
+    priority: u8,
+    spans: Vec<Span<'static>>,
+}
 
-    let mut app = App::new(tx, rx, tg_rx);
-    app.fetch_rss();
+impl FooterSegment {
+    fn new(priority: u8, spans: Vec<Span<'static>>) -> Self {
+        Self { priority, spans }
+    }
 
-    let ui_tg_tx = tg_tx.clone();
-    tokio::spawn(async move {
-        let _ = monitor.monitor(tg_client, target_ids, ui_tg_tx).await;
-    });
+    fn width(&self) -> usize {
+        self.spans.iter().map(Span::width).sum()
+    }
+}
 
-    enable_raw_mode()?;
-    stdout().execute(EnterAlternateScreen)?;
-    let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+fn footer_segments(app: &App, time_left: f32) -> Vec<FooterSegment> {
+    let theme = &app.theme;
+    let (mode, hints) = key_hints(app);
+    let hints: Vec<String> = hints.into_iter().map(|(key, what)| format!("[{}] {}", key, what)).collect();
+    let mut segments = vec![
+        FooterSegment::new(0, vec![
+            Span::styled(format!(" {} ", mode), Style::default().bg(theme.chrome).fg(theme.background).bold()),
+            Span::styled("", Style::default().fg(theme.chrome).bg(theme.border)),
+            Span::styled(format!(" {} ", hints.join("   ")), Style::default().bg(theme.border).fg(theme.text)),
+            Span::styled("", Style::default().fg(theme.border)),
+        ]),
+        FooterSegment::new(1, vec![Span::raw(format!("   Syncing in: {:.0}s", time_left.max(0.0)))]),
+        FooterSegment::new(1, vec![Span::styled(format!("   {}", app.now().format("%H:%M")), Style::default().fg(theme.text).bold())]),
+    ];
+    let item_count: usize = app.rss_feeds.iter().map(|feed| feed.items.len()).sum();
+    let mut summary = format!("   {} / {}", counted(item_count, "item"), counted(app.rss_feeds.len(), "feed"));
+    if !app.telegram_disabled {
+        summary.push_str(&format!(" / {} unread", counted(app.unread_chats(), "chat")));
+    }
+    segments.push(FooterSegment::new(2, vec![Span::styled(summary, Style::default().fg(theme.muted))]));
+    if !app.retrying.is_empty() {
+        let text = format!("   ↻ retrying {}", counted(app.retrying.len(), "feed"));
+        segments.push(FooterSegment::new(1, vec![Span::styled(text, Style::default().fg(theme.highlight))]));
+    }
+    if app.paused {
+        segments.push(FooterSegment::new(1, vec![Span::styled("   ⏸ PAUSED", Style::default().fg(theme.highlight).bold())]));
+    }
+    let per_block = app.items_per_block.map_or("fit".to_string(), |n| n.to_string());
+    segments.push(FooterSegment::new(2, vec![Span::styled(format!("   ≡ {} per block", per_block), Style::default().fg(theme.muted).italic())]));
+    if app.sort_by_score {
+        segments.push(FooterSegment::new(2, vec![Span::styled("   ▲ by score", Style::default().fg(theme.muted).italic())]));
+    }
+    if app.feed_query_editing || !app.feed_query.is_empty() {
+        let cursor = if app.feed_query_editing { "▏" } else { "" };
+        segments.push(FooterSegment::new(1, vec![
+            Span::styled(format!("   /{}{}", app.feed_query, cursor), Style::default().fg(theme.text)),
+            Span::styled(format!(" {} matches", app.feed_matches()), Style::default().fg(theme.muted).italic()),
+        ]));
+    }
+    if let Some(category) = &app.category_filter {
+        segments.push(FooterSegment::new(1, vec![Span::styled(format!("   #{}", category), Style::default().fg(theme.highlight))]));
+    }
+    if app.telegram_disabled {
+        segments.push(FooterSegment::new(2, vec![Span::styled("   Telegram off", Style::default().fg(theme.muted).italic())]));
+    }
+    if app.is_quiet() {
+        segments.push(FooterSegment::new(2, vec![Span::styled("   ☾ quiet", Style::default().fg(theme.muted).italic())]));
+    }
+    if let Some(notice) = &app.notice
+        && Instant::now() < notice.until
+    {
+        let color = if notice.is_error { theme.error } else { theme.highlight };
+        segments.push(FooterSegment::new(0, vec![Span::styled(format!("   {}", notice.text), Style::default().fg(color))]));
+    }
+    segments
+}
 
-    let tick_rate = Duration::from_secs(15);
-    let mut last_tick = Instant::now();
+/// The footer badge and key hints for whatever currently takes the keyboard, checked in the
+/// same order as the main loop hands out key presses.
+fn key_hints(app: &App) -> (&'static str, Vec<(String, &'static str)>) {
+    let key = |action| app.config.keymap.key_for(action).map(actions::key_label).unwrap_or_default();
+    let fixed = |label: &str| label.to_string();
+    if app.feed_query_editing || app.telegram_query_editing {
+        ("SEARCH", vec![(fixed("Enter"), "KEEP"), (fixed("Esc"), "CLEAR")])
+    } else if app.palette.is_some() {
+        ("COMMANDS", vec![(fixed("↑↓"), "SELECT"), (fixed("Enter"), "RUN"), (fixed("Esc"), "CLOSE")])
+    } else if app.help {
+        ("HELP", vec![(format!("{}/{}", key(Action::Help), key(Action::Close)), "CLOSE")])
+    } else if app.reader.is_some() {
+        let hints = vec![
+            (fixed("↑↓"), "SCROLL"),
+            (key(Action::CopyLink), "COPY LINK"),
+            (key(Action::SaveItem), "SAVE"),
+            (key(Action::Close), "CLOSE"),
+        ];
+        ("READER", hints)
+    } else if app.digest.is_some() {
+        ("DIGEST", vec![(key(Action::Close), "CLOSE")])
+    } else {
+        match (app.view, app.focused) {
+            (ViewMode::Timeline, _) => {
+                let hints = vec![
+                    (fixed("↑↓"), "SELECT"),
+                    (key(Action::ReadItem), "READ"),
+                    (key(Action::ToggleBookmark), "STAR"),
+                    (key(Action::Close), "BACK"),
+                ];
+                ("TIMELINE", hints)
+            }
+            (ViewMode::Bookmarks, _) => {
+                let hints = vec![
+                    (fixed("↑↓"), "SELECT"),
+                    (key(Action::ReadItem), "OPEN"),
+                    (key(Action::ToggleBookmark), "UNSTAR"),
+                    (key(Action::Close), "BACK"),
+                ];
+                ("STARRED", hints)
+            }
+            (ViewMode::Focused(_), _) => {
+                ("FEED", vec![(fixed("↑↓"), "SCROLL"), (key(Action::ReadItem), "READ"), (key(Action::Close), "BACK")])
+            }
+            (ViewMode::Grid, Some(Panel::Feed(_))) => {
+                let hints = vec![
+                    (fixed("↑↓"), "SCROLL"),
+                    (key(Action::ReadItem), "READ"),
+                    (key(Action::ToggleBookmark), "STAR"),
+                    (key(Action::NextPanel), "NEXT"),
+                    (key(Action::Close), "UNFOCUS"),
+                ];
+                ("FEED", hints)
+            }
+            (ViewMode::Grid, Some(Panel::Telegram(_))) => {
+                let hints = vec![
+                    (fixed("↑↓"), "SCROLL"),
+                    (key(Action::Search), "FILTER"),
+                    (key(Action::NextPanel), "NEXT"),
+                    (key(Action::Close), "UNFOCUS"),
+                ];
+                ("TELEGRAM", hints)
+            }
+            (ViewMode::Grid, None) => {
+                let hints = vec![
+                    (key(Action::Quit), "QUIT"),
+                    (key(Action::Refresh), "REFRESH"),
+                    (key(Action::NextPanel), "FOCUS"),
+                    (key(Action::CommandPalette), "COMMANDS"),
+                    (key(Action::Help), "HELP"),
+                ];
+                ("SYSTEM", hints)
+            }
+        }
+    }
+}
+
+/// "1 item", "2 items".
+fn counted(n: usize, noun: &str) -> String {
+    if n == 1 { format!("1 {}", noun) } else { format!("{} {}s", n, noun) }
+}
 
+/// Drops the least important segments until the footer fits, using a second row in `Wrap` mode.
+fn fit_footer(mut segments: Vec<FooterSegment>, width: usize, overflow: FooterOverflow) -> Vec<Line<'static>> {
+    let max_rows = match overflow {
+        FooterOverflow::Truncate => 1,
+        FooterOverflow::Wrap => 2,
+    };
     loop {
-        while let Ok(new_feeds) = app.rx.try_recv() {
-            app.rss_feeds = new_feeds;
-            app.offset = 0;
+        let rows = pack_footer_rows(&segments, width);
+        if rows.len() <= max_rows || segments.len() <= 1 {
+            return rows.into_iter().take(max_rows).map(Line::from).collect();
         }
-        while let Ok((sender, msg)) = app.tg_rx.try_recv() {
-            app.telegram_messages.insert(sender, msg);
+        // Last-added segment goes first among equal priorities
+        if let Some(drop) = segments.iter().enumerate().max_by_key(|(i, seg)| (seg.priority, *i)).map(|(i, _)| i) {
+            segments.remove(drop);
         }
+    }
+}
 
-        terminal.draw(|frame| {
-            let area = frame.area();
-            frame.render_widget(Block::default().bg(DARK_BG), area);
+fn pack_footer_rows(segments: &[FooterSegment], width: usize) -> Vec<Vec<Span<'static>>> {
+    let mut rows = vec![Vec::new()];
+    let mut used = 0;
+    for seg in segments {
+        if used > 0 && used + seg.width() > width {
+            rows.push(Vec::new());
+            used = 0;
+        }
+        if let Some(row) = rows.last_mut() {
+            row.extend(seg.spans.iter().cloned());
+        }
+        used += seg.width();
+    }
+    rows
+}
 
-            let main_layout = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([Constraint::Min(10), Constraint::Length(1)])
-                .split(area);
+/// Stands in for everything else on a terminal too small to lay out; keys still work.
+fn render_too_small(frame: &mut Frame, area: Rect, theme: &Theme) {
+    let lines = vec![
+        Line::from(Span::styled("Terminal too small", Style::default().fg(theme.highlight).bold())),
+        Line::from(Span::styled(
+            format!("{}×{}, needs {}×{}", area.width, area.height, MIN_COLUMNS, MIN_ROWS),
+            Style::default().fg(theme.muted),
+        )),
+    ];
+    let top = area.height.saturating_sub(lines.len() as u16) / 2;
+    let text_area = Rect::new(area.x, area.y + top, area.width, area.height - top);
+    frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center).wrap(Wrap { trim: true }), text_area);
+}
 
-            let columns = Layout::default()
-                .direction(Direction::Horizontal)
-                .constraints([
-                    Constraint::Percentage(40), // Hacker/IT RSS
-                    Constraint::Percentage(40), // General News RSS
-                    Constraint::Percentage(20), // Telegram
-                ])
-                .split(main_layout[0]);
+/// The newest log entries that fit, oldest at the top like a terminal scrollback.
+fn render_log(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let key = app.config.keymap.key_for(Action::ToggleLog).map(actions::key_label).unwrap_or_default();
+    let title = format!(" LOG · {} of {} [{}] hide ", app.log.len(), LOG_CAPACITY, key);
+    let block = create_block(title, theme.chrome, theme);
+    let inner = block.inner(area);
+    let skip = app.log.len().saturating_sub(inner.height as usize);
+    let lines: Vec<Line> = app
+        .log
+        .iter()
+        .skip(skip)
+        .map(|entry| {
+            let (tag, color) = match entry.level {
+                LogLevel::Info => ("INFO ", theme.muted),
+                LogLevel::Warn => ("WARN ", theme.highlight),
+                LogLevel::Error => ("ERROR", theme.error),
+            };
+            Line::from(vec![
+                Span::styled(format!(" {} ", entry.at.format("%H:%M:%S")), Style::default().fg(theme.muted)),
+                Span::styled(format!("{} ", tag), Style::default().fg(theme.adapt(color)).bold()),
+                Span::styled(entry.text.clone(), Style::default().fg(if entry.level == LogLevel::Info { theme.text } else { theme.adapt(color) })),
+            ])
+        })
+        .collect();
+    frame.render_widget(block, area);
+    frame.render_widget(Paragraph::new(lines), inner);
+}
 
-            // --- Column 1: Hacker/IT RSS (Stacked) ---
-            let left_rss_layout = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([Constraint::Ratio(1, 3), Constraint::Ratio(1, 3), Constraint::Ratio(1, 3)])
-                .split(columns[0]);
+/// A rect of the given percentage size centred in `area`, for popups.
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(vertical[1])[1]
+}
 
-            let left_titles = [" THE HACKER NEWS ", " COMPUTER WEEKLY ", " SOFTWARE DEV TIMES "];
-            for (idx, &sub_area) in left_rss_layout.iter().enumerate() {
-                render_rss_block(frame, sub_area, &app, idx, left_titles[idx], MATRIX_GREEN, 2, None);
-            }
+/// Plain numbered list of a feed's headlines, easy to read or select and copy.
+fn render_digest(frame: &mut Frame, area: Rect, app: &App, feed_idx: usize) {
+    let theme = &app.theme;
+    let popup = centered_rect(70, 80, area);
+    let fetched = app.fetched_at[feed_idx]
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "not fetched yet".to_string());
+
+    let mut lines = vec![
+        Line::from(Span::styled(app.config.feeds[feed_idx].title.trim(), Style::default().bold().fg(theme.text))),
+        Line::from(Span::styled(format!("Fetched {}", fetched), Style::default().fg(theme.muted).italic())),
+        Line::from(""),
+    ];
+    for (n, item) in app.visible_items(feed_idx).into_iter().enumerate() {
+        lines.push(Line::from(format!("{:>2}. {}", n + 1, item.title)));
+    }
+
+    frame.render_widget(Clear, popup);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(create_block(" DIGEST ", theme.chrome, theme).bg(theme.background).fg(theme.text)),
+        popup,
+    );
+}
+
+fn render_reader(frame: &mut Frame, area: Rect, reader: &Reader, tz: Option<chrono_tz::Tz>, theme: &Theme) {
+    let popup = centered_rect(70, 80, area);
+    let block = create_block(" READ ", theme.chrome, theme).bg(theme.background).fg(theme.text);
+    let inner = block.inner(popup);
+    frame.render_widget(Clear, popup);
+    frame.render_widget(block, popup);
 
-            // --- Column 2: News RSS (Stacked) ---
-            let mid_rss_layout = Layout::default()
+    // The image sits above the text and takes at most half the pane
+    let text_area = match &reader.image {
+        Some(image) => {
+            let split = Layout::default()
                 .direction(Direction::Vertical)
-                .constraints([Constraint::Ratio(1, 3), Constraint::Ratio(1, 3), Constraint::Ratio(1, 3)])
-                .split(columns[1]);
+                .constraints([Constraint::Length((inner.height / 2).min(READER_IMAGE_ROWS)), Constraint::Min(1)])
+                .split(inner);
+            frame.render_stateful_widget(StatefulImage::default(), split[0], &mut *image.borrow_mut());
+            split[1]
+        }
+        None => inner,
+    };
+    let width = text_area.width as usize;
+    let item = &reader.item;
 
-            let mid_configs = [
-                (" STOCKS ", WORLD_MAGENTA, "Stocks"),
-                (" WORLD NEWS", SPORTS_CYAN, "World"),
-                (" LOCAL NEWS ", NEWS_GOLD, "Singapore"),
-            ];
+    let mut lines: Vec<Line> = wrap_text(&item.title, width)
+        .into_iter()
+        .map(|row| Line::from(Span::styled(row, Style::default().bold().fg(theme.text))))
+        .collect();
+    lines.push(Line::from(Span::styled(item.full_date(tz), Style::default().fg(theme.muted).italic())));
+    if !item.link.is_empty() {
+        lines.push(Line::from(Span::styled(item.link.clone(), Style::default().fg(theme.chrome).underlined())));
+    }
+    // Without inline graphics (or until it has downloaded), the image is just a link
+    if reader.image.is_none()
+        && let Some(url) = &item.image
+    {
+        lines.push(Line::from(Span::styled(format!("image: {}", url), Style::default().fg(theme.muted).underlined())));
+    }
+    lines.push(Line::from(""));
+    lines.extend(wrap_text(item.body(), width).into_iter().map(|row| Line::from(Span::styled(row, Style::default().fg(theme.text)))));
 
-            for (i, &sub_area) in mid_rss_layout.iter().enumerate() {
-                let (title, color, tag) = mid_configs[i];
-                render_rss_block(frame, sub_area, &app, i + 3, title, color, 2, Some((tag, color)));
-            }
+    let visible = text_area.height as usize;
+    reader.max_scroll.set(lines.len().saturating_sub(visible) as u16);
+    let scroll = reader.scroll.min(reader.max_scroll.get());
 
-            // --- Column 3: Telegram ---
-            let tg_items: Vec<ListItem> = app.telegram_messages.iter().rev().take(20).map(|(s, m)| {
-                ListItem::new(vec![
-                    Line::from(vec![
-                        Span::styled(" ● ", Style::default().fg(TELEGRAM_BLUE)), 
-                        Span::styled(s, Style::default().bold().fg(TELEGRAM_BLUE))
-                    ]),
-                    Line::from(vec![Span::raw("   "), Span::raw(m)]),
-                    Line::from(""),
-                ])
-            }).collect();
-            frame.render_widget(List::new(tg_items).block(create_block(" TELEGRAM ", TELEGRAM_BLUE)), columns[2]);
+    frame.render_widget(Paragraph::new(lines).scroll((scroll, 0)), text_area);
+}
 
-            // --- Footer ---
-            let time_left = tick_rate.as_secs_f32() - last_tick.elapsed().as_secs_f32();
-            let footer = Paragraph::new(Line::from(vec![
-                Span::styled(" SYSTEM ", Style::default().bg(UI_GREY).fg(DARK_BG).bold()),
-                Span::styled("", Style::default().fg(UI_GREY).bg(BORDER_MUTED)),
-                Span::styled(" [Q] QUIT   [R] REFRESH ", Style::default().bg(BORDER_MUTED).fg(Color::White)),
-                Span::styled("", Style::default().fg(BORDER_MUTED)),
-                Span::raw(format!("   Syncing in: {:.0}s", time_left.max(0.0))),
-            ]));
-            frame.render_widget(footer, main_layout[1]);
-        })?;
+fn render_palette(frame: &mut Frame, area: Rect, palette: &Palette, keymap: &Keymap, theme: &Theme) {
+    let popup = centered_rect(50, 50, area);
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(" > ", Style::default().fg(theme.chrome).bold()),
+            Span::styled(palette.query.clone(), Style::default().fg(theme.text)),
+            Span::styled("▏", Style::default().fg(theme.chrome)),
+        ]),
+        Line::from(""),
+    ];
+    for (i, action) in actions::filter_actions(&palette.query).into_iter().enumerate() {
+        let key = keymap.key_for(action).map(actions::key_label).unwrap_or_default();
+        let style = if i == palette.selected {
+            Style::default().bg(theme.border).fg(theme.text).bold()
+        } else {
+            Style::default().fg(theme.chrome)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("   {:<32}", action.name()), style),
+            Span::styled(format!("{:>5} ", key), Style::default().fg(theme.muted)),
+        ]));
+    }
 
-        if event::poll(Duration::from_millis(100))? {
-            if let event::Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    match key.code {
-                        KeyCode::Char('q') => break,
-                        KeyCode::Char('r') => app.fetch_rss(),
-                        _ => {}
-                    }
-                }
+    frame.render_widget(Clear, popup);
+    frame.render_widget(Paragraph::new(lines).block(create_block(" COMMANDS ", theme.chrome, theme).bg(theme.background).fg(theme.text)), popup);
+}
+
+/// Every action with its keys, straight from the binding table so it can't drift from the handlers.
+/// Splits into two columns when one doesn't fit the popup.
+fn render_help(frame: &mut Frame, area: Rect, keymap: &Keymap, theme: &Theme) {
+    let popup = centered_rect(70, 80, area);
+    let row = |keys: String, what: &str| {
+        Line::from(vec![
+            Span::styled(format!("  {:>10}  ", keys), Style::default().fg(theme.chrome).bold()),
+            Span::styled(what.to_string(), Style::default().fg(theme.text)),
+        ])
+    };
+    let mut lines: Vec<Line> = Action::ALL
+        .iter()
+        .map(|&action| {
+            let keys: Vec<String> = keymap.keys_for(action).into_iter().map(actions::key_label).collect();
+            row(keys.join(" / "), action.name())
+        })
+        .collect();
+    lines.extend(actions::FIXED_KEYS.iter().map(|&(keys, what)| row(keys.to_string(), what)));
+
+    let block = create_block(" KEYS [?] close ", theme.chrome, theme).bg(theme.background).fg(theme.text);
+    let inner = block.inner(popup);
+    frame.render_widget(Clear, popup);
+    frame.render_widget(block, popup);
+    if lines.len() <= inner.height as usize {
+        frame.render_widget(Paragraph::new(lines), inner);
+        return;
+    }
+    let halves = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+        .split(inner);
+    let right = lines.split_off(lines.len().div_ceil(2));
+    frame.render_widget(Paragraph::new(lines), halves[0]);
+    frame.render_widget(Paragraph::new(right), halves[1]);
+}
+
+fn create_block<'a>(title: impl Into<Span<'a>>, color: Color, theme: &Theme) -> Block<'a> {
+    Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.border))
+        .title(title.into().patch_style(Style::default().fg(theme.adapt(color)).bold()))
+}
+
+/// Shared line editing for the search prompts: Enter keeps the query, Esc clears it.
+fn edit_query(query: &mut String, editing: &mut bool, code: KeyCode) {
+    match code {
+        KeyCode::Esc => {
+            query.clear();
+            *editing = false;
+        }
+        KeyCode::Enter => *editing = false,
+        KeyCode::Backspace => {
+            query.pop();
+        }
+        KeyCode::Char(c) => query.push(c),
+        _ => {}
+    }
+}
+
+/// Splits `text` into spans, picking out case-insensitive matches of `query` (already lowercased).
+fn highlight_matches(text: &str, query: &str, base: Style, theme: &Theme) -> Vec<Span<'static>> {
+    if query.is_empty() {
+        return vec![Span::styled(text.to_string(), base)];
+    }
+    // Lowercase the whole text, remembering which character each lowercase one came from, so a
+    // match maps back even where lowercasing changes the length ('İ' becomes two characters)
+    let chars: Vec<char> = text.chars().collect();
+    let (mut lower, mut origin) = (Vec::new(), Vec::new());
+    for (i, c) in chars.iter().enumerate() {
+        for l in c.to_lowercase() {
+            lower.push(l);
+            origin.push(i);
+        }
+    }
+    let needle: Vec<char> = query.to_lowercase().chars().collect();
+    // Matches only count when they start and end on whole characters of the original
+    let boundary = |j: usize| j == 0 || j == lower.len() || origin[j] != origin[j - 1];
+    let mut spans = Vec::new();
+    let (mut done, mut j) = (0, 0);
+    while j + needle.len() <= lower.len() {
+        let end = j + needle.len();
+        if lower[j..end] == needle[..] && boundary(j) && boundary(end) {
+            let (start_char, end_char) = (origin[j], origin[end - 1] + 1);
+            if start_char > done {
+                spans.push(Span::styled(chars[done..start_char].iter().collect::<String>(), base));
             }
+            spans.push(Span::styled(chars[start_char..end_char].iter().collect::<String>(), base.bg(theme.highlight).fg(theme.background)));
+            done = end_char;
+            j = end;
+        } else {
+            j += 1;
         }
+    }
+    if done < chars.len() {
+        spans.push(Span::styled(chars[done..].iter().collect::<String>(), base));
+    }
+    spans
+}
 
-        if last_tick.elapsed() >= tick_rate {
-            app.on_tick();
-            last_tick = Instant::now();
+/// Wraps on word boundaries, only splitting a single token (long URLs, code) when it is wider than `width`.
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    textwrap::wrap(text, width.max(1))
+        .into_iter()
+        .map(|row| row.into_owned())
+        .collect()
+}
+
+/// `wrap_text` kept to `max_rows`; when rows are dropped the last one kept ends in '…', wrapped one
+/// column narrower so the mark still fits.
+fn wrap_clipped(text: &str, width: usize, max_rows: usize) -> Vec<String> {
+    let rows = wrap_text(text, width);
+    if rows.len() <= max_rows {
+        return rows;
+    }
+    let mut rows = wrap_text(text, width.saturating_sub(1));
+    rows.truncate(max_rows);
+    if let Some(last) = rows.last_mut() {
+        last.push('…');
+    }
+    rows
+}
+
+/// Cuts `text` to at most `width` terminal cells, ending in "..." when shortened.
+/// Wide (CJK, emoji) characters count double and grapheme clusters are never split.
+fn truncate_to_width(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    // Too narrow for any text before the ellipsis, so show as much of the ellipsis as fits
+    if width < 3 {
+        return ".".repeat(width);
+    }
+    let budget = width - 3;
+    let mut used = 0;
+    let mut out = String::new();
+    for grapheme in text.graphemes(true) {
+        used += grapheme.width();
+        if used > budget {
+            break;
         }
+        out.push_str(grapheme);
     }
+    out.push_str("...");
+    out
+}
 
-    disable_raw_mode()?;
-    stdout().execute(LeaveAlternateScreen)?;
-    Ok(())
+/// Splits a title too wide for `first_width` at a word boundary, returning the first row and the
+/// rest cut to `rest_width`. A title that fits comes back whole with no second row.
+fn wrap_title(text: &str, first_width: usize, rest_width: usize) -> (String, Option<String>) {
+    if text.width() <= first_width {
+        return (text.to_string(), None);
+    }
+    let rows = wrap_text(text, first_width);
+    // The rest of the original text, so a word split mid-way carries straight on rather than gaining a space
+    let rest = text.strip_prefix(rows[0].as_str()).map_or_else(|| rows[1..].join(" "), |rest| rest.trim_start().to_string());
+    (rows[0].clone(), Some(truncate_to_width(&rest, rest_width)))
 }
 
-fn create_block<'a>(title: impl Into<Span<'a>>, color: Color) -> Block<'a> {
-    Block::default()
-        .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(BORDER_MUTED))
-        .title(title.into().patch_style(Style::default().fg(color).bold()))
+/// Staleness of a feed block, to the second while it's fresh.
+fn updated_ago(at: DateTime<Local>, now: DateTime<Local>) -> String {
+    let age = now - at;
+    if age.num_minutes() < 1 {
+        format!("{}s ago", age.num_seconds().max(0))
+    } else if age.num_hours() < 1 {
+        format!("{}m ago", age.num_minutes())
+    } else {
+        format!("{}h ago", age.num_hours())
+    }
+}
+
+/// "just now", "5m ago", "3h ago", then the date and time (in the item-date zone) once it's a day old.
+fn message_age(sent: DateTime<Utc>, now: DateTime<Local>, tz: Option<chrono_tz::Tz>) -> String {
+    let age = now.with_timezone(&Utc) - sent;
+    if age.num_minutes() < 1 {
+        "just now".to_string()
+    } else if age.num_hours() < 1 {
+        format!("{}m ago", age.num_minutes())
+    } else if age.num_days() < 1 {
+        format!("{}h ago", age.num_hours())
+    } else {
+        const FORMAT: &str = "%d %b %H:%M";
+        match tz {
+            Some(tz) => sent.with_timezone(&tz).format(FORMAT).to_string(),
+            None => sent.with_timezone(&Local).format(FORMAT).to_string(),
+        }
+    }
+}
+
+fn render_telegram_panel(frame: &mut Frame, area: Rect, app: &App, panel_idx: usize, panel: &TelegramPanel) {
+    let theme = &app.theme;
+    app.hits.borrow_mut().push((area, Hit::Telegram(panel_idx)));
+    let show_status = panel_idx == 0;
+    let tg_text_width = (area.width as usize).saturating_sub(2 + 3);
+    let mut tg_items: Vec<ListItem> = Vec::new();
+    if show_status && !app.telegram_configured {
+        tg_items.push(ListItem::new(vec![
+            Line::from(Span::styled(" Telegram not configured", Style::default().fg(theme.muted).bold())),
+            Line::from(Span::styled("   set TG_API_ID and TG_API_HASH in .env", Style::default().fg(theme.muted))),
+            Line::from(""),
+        ]));
+    }
+    if show_status && app.telegram_offline {
+        tg_items.push(ListItem::new(vec![
+            Line::from(Span::styled(" ⚠ Telegram offline", Style::default().fg(theme.highlight).bold())),
+            Line::from(Span::styled("   [T] connect", Style::default().fg(theme.muted))),
+            Line::from(""),
+        ]));
+    }
+    if show_status
+        && let Some(ConnectionState::Reconnecting { error, .. } | ConnectionState::Restarting { error, .. }) = &app.telegram_status
+    {
+        for row in wrap_text(&format!("Connection lost: {}", error), tg_text_width) {
+            tg_items.push(ListItem::new(Line::from(vec![Span::raw("   "), Span::styled(row, Style::default().fg(theme.muted))])));
+        }
+    }
+    if show_status && let Some(err) = &app.telegram_error {
+        for row in wrap_text(err, tg_text_width) {
+            tg_items.push(ListItem::new(Line::from(vec![Span::raw("   "), Span::styled(row, Style::default().fg(theme.muted))])));
+        }
+    }
+    let query = app.telegram_query.to_lowercase();
+    let messages = app.panel_messages(panel);
+    // A filter typed while scrolled back can leave fewer messages than the offset
+    let scroll = app.telegram_scroll[panel_idx].min(messages.len().saturating_sub(1));
+    // Every wrapped row of a message sits at the same indent, just inside the bullet
+    tg_items.extend(messages.into_iter().skip(scroll).take(TELEGRAM_SHOWN).map(|m| {
+        let mut header = vec![Span::styled(" ● ", Style::default().fg(theme.telegram))];
+        header.extend(highlight_matches(&m.sender, &query, Style::default().bold().fg(theme.telegram), theme));
+        header.push(Span::styled(format!(" · {}", message_age(m.timestamp, app.now(), app.config.timezone)), Style::default().fg(theme.muted).italic()));
+        let mut lines = vec![Line::from(header)];
+        for row in wrap_text(&m.text, tg_text_width) {
+            let mut spans = vec![Span::raw("   ")];
+            spans.extend(highlight_matches(&row, &query, Style::default(), theme));
+            lines.push(Line::from(spans));
+        }
+        lines.push(Line::from(""));
+        ListItem::new(lines)
+    }));
+
+    let mut title = format!(" {} ", panel.title);
+    if scroll > 0 {
+        title.push_str(&format!("↑{} newer ", scroll));
+    }
+    if app.telegram_query_editing || !app.telegram_query.is_empty() {
+        let cursor = if app.telegram_query_editing { "▏" } else { "" };
+        title.push_str(&format!("/{}{} ", app.telegram_query, cursor));
+    }
+    let mut block = create_block(title, theme.telegram, theme);
+    if let Some(status) = connection_label(app.telegram_status.as_ref(), theme) {
+        block = block.title_top(status.right_aligned());
+    }
+    if app.focused == Some(Panel::Telegram(panel_idx)) {
+        block = block.border_style(Style::default().fg(theme.telegram));
+    }
+    frame.render_widget(List::new(tg_items).block(block), area);
 }
 
+/// A coloured dot for the monitor's connection, with a word when it isn't simply connected.
+fn connection_label(status: Option<&ConnectionState>, theme: &Theme) -> Option<Line<'static>> {
+    let (text, color) = match status? {
+        ConnectionState::Connected => (" ● ".to_string(), theme.telegram),
+        ConnectionState::Reconnecting { attempt, .. } => (format!(" ● reconnecting ({}) ", attempt), theme.highlight),
+        ConnectionState::Restarting { restart, max, .. } => (format!(" ● restarting ({}/{}) ", restart, max), theme.highlight),
+        ConnectionState::Failed(_) => (" ● disconnected ".to_string(), theme.error),
+    };
+    Some(Line::from(Span::styled(text, Style::default().fg(color).bold())))
+}
+
+#[allow(clippy::too_many_arguments)]
 fn render_rss_block(
     frame: &mut Frame, 
     area: Rect, 
@@ -243,17 +2559,67 @@ fn render_rss_block(
     count: usize,
     tag_info: Option<(&str, Color)>
 ) {
+    let theme = &app.theme;
+    let color = theme.adapt(color);
+    let tag_info = tag_info.map(|(tag, tag_color)| (tag, theme.adapt(tag_color)));
     let mut items = Vec::new();
     let inner_width = (area.width as usize).saturating_sub(2);
+    let inner = area.inner(Margin::new(1, 1));
+    app.hits.borrow_mut().push((area, Hit::Feed(feed_idx)));
+    // Top row of the next item, for its click area
+    let mut next_row = inner.y;
 
-    if let Some(feed) = app.rss_feeds.get(feed_idx) {
+    if feed_idx < app.rss_feeds.len() {
+        let feed = app.visible_items(feed_idx);
+        let query = app.feed_query.to_lowercase();
         if !feed.is_empty() {
-            for i in 0..count {
-                let item_idx = (app.offset + i) % feed.len();
-                let (title_text, date, desc) = &feed[item_idx];
+            // Rows kept free under the items for the load-more hint, rate limit and error lines
+            let trailer_rows = usize::from(
+                app.loading_more[feed_idx] || (app.focused_feed() == Some(feed_idx) && app.rss_feeds[feed_idx].next_page.is_some()),
+            ) + usize::from(app.rate_limited_until[feed_idx].is_some())
+                + usize::from(app.errors[feed_idx].is_some() && app.config.error_display != ErrorDisplay::Compact);
+            let budget = (area.height as usize).saturating_sub(2 + trailer_rows);
+            // Each item is measured (headline, its summary rows, a separator before all but the first)
+            // and packed until the next one would be clipped; the first always goes in. Packing runs
+            // past `count` to learn how many would fit, but only the first `count` are drawn. It also
+            // stops at the end of the feed, so a narrow search doesn't repeat items.
+            let mut item_indices = Vec::new();
+            let mut desc_rows: Vec<Vec<String>> = Vec::new();
+            let (mut fits, mut fill_rows, mut used_rows) = (0, 0, 0);
+            for i in 0..feed.len() {
+                let item_idx = (app.offsets[feed_idx] + i) % feed.len();
+                let rows: Vec<String> = wrap_text(&feed[item_idx].summary.replace('\n', " "), inner_width).into_iter().take(2).collect();
+                let height = 1 + rows.len() + usize::from(i > 0);
+                if i > 0 && fill_rows + height > budget {
+                    break;
+                }
+                fits += 1;
+                fill_rows += height;
+                if i < count {
+                    used_rows += height;
+                    item_indices.push(item_idx);
+                    desc_rows.push(rows);
+                }
+            }
+            let count = item_indices.len();
+            app.fits[feed_idx].set(fits);
+            // Rows left over once every item and separator is drawn; each wrapped title spends one
+            let mut spare_rows = budget.saturating_sub(used_rows);
+            for (i, (&item_idx, desc_rows)) in item_indices.iter().zip(desc_rows).enumerate() {
+                let FeedItem { title: title_text, summary: desc, .. } = feed[item_idx];
                 
-                let date_str = date.chars().take(10).collect::<String>();
-                let label_prefix = "◆ ";
+                let date_str = feed[item_idx].short_date(app.config.timezone);
+                let prominent = app.config.score(&app.config.feeds[feed_idx].url, title_text, desc) >= app.config.highlight_score;
+                let label_prefix = if prominent { "▲ " } else { "◆ " };
+                let new_marker = if app.new_items[feed_idx].contains(feed[item_idx].key()) { "NEW " } else { "" };
+                let star = if app.is_bookmarked(feed_idx, feed[item_idx]) { "★ " } else { "" };
+                let title_color = if prominent {
+                    color
+                } else if app.read.contains(feed[item_idx].key()) {
+                    theme.muted
+                } else {
+                    theme.text
+                };
                 
                 // Calculate tag width if it exists
                 let (tag_str, tag_color) = match tag_info {
@@ -261,48 +2627,465 @@ fn render_rss_block(
                     None => ("".to_string(), Color::Reset),
                 };
 
-                let prefix_len = label_prefix.chars().count();
-                let tag_len = tag_str.chars().count();
+                // Every width below is in terminal columns, so localized dates and wide tags line up
+                let prefix_len = label_prefix.width() + star.width() + new_marker.width();
+                let tag_len = tag_str.width();
+                let date_len = date_str.width();
                 
                 // Max width title can take: Total - date - tag - prefix - padding
-                let max_title_len = inner_width.saturating_sub(date_str.len() + tag_len + prefix_len + 2);
-                let truncated_title = if title_text.chars().count() > max_title_len {
-                    format!("{}...", title_text.chars().take(max_title_len.saturating_sub(3)).collect::<String>())
+                let mut max_title_len = inner_width.saturating_sub(date_len + tag_len + prefix_len + 2);
+                // Optional readability cap for very wide blocks; the stricter limit wins
+                if let Some(cap) = app.config.max_title_chars {
+                    max_title_len = max_title_len.min(cap);
+                }
+                let (truncated_title, title_rest) = if app.config.title_overflow == TitleOverflow::Wrap && spare_rows > 0 {
+                    // The second row has no date, so it runs to the block edge (within the cap)
+                    let rest_width = inner_width.saturating_sub(prefix_len + 1);
+                    let rest_width = app.config.max_title_chars.map_or(rest_width, |cap| rest_width.min(cap));
+                    wrap_title(title_text, max_title_len, rest_width)
                 } else {
-                    title_text.clone()
+                    (truncate_to_width(title_text, max_title_len), None)
                 };
+                if title_rest.is_some() {
+                    spare_rows -= 1;
+                }
 
                 // Alignment padding
-                let current_content_len = prefix_len + truncated_title.chars().count() + date_str.len() + tag_len + 1;
+                let current_content_len = prefix_len + truncated_title.width() + date_len + tag_len + 1;
                 let padding = " ".repeat(inner_width.saturating_sub(current_content_len));
 
-                let header_line = Line::from(vec![
+                let mut header_spans = vec![
                     Span::styled(label_prefix, Style::default().fg(color)),
-                    Span::styled(truncated_title, Style::default().bold().fg(Color::White)),
+                    Span::styled(star, Style::default().fg(theme.highlight)),
+                    Span::styled(new_marker, Style::default().fg(color).bold()),
+                ];
+                header_spans.extend(highlight_matches(&truncated_title, &query, Style::default().bold().fg(title_color), theme));
+                header_spans.extend([
                     Span::raw(padding),
-                    Span::styled(date_str, Style::default().fg(DESC_GREY).italic()),
+                    Span::styled(date_str, Style::default().fg(theme.muted).italic()),
                     Span::styled(tag_str, Style::default().fg(tag_color).bold()),
                 ]);
+                let header_line = Line::from(header_spans);
 
                 let mut item_lines = vec![header_line];
-                let clean_desc = desc.replace('\n', " ");
-                for chunk in clean_desc.chars().collect::<Vec<char>>().chunks(inner_width).take(2) {
-                    item_lines.push(Line::from(vec![
-                        Span::styled(chunk.iter().collect::<String>(), Style::default().fg(DESC_GREY)),
-                    ]));
+                if let Some(rest) = title_rest {
+                    // Indented to line up under the first row of the title
+                    let mut spans = vec![Span::raw(" ".repeat(prefix_len))];
+                    spans.extend(highlight_matches(&rest, &query, Style::default().bold().fg(title_color), theme));
+                    item_lines.push(Line::from(spans));
+                }
+                for row in desc_rows {
+                    item_lines.push(Line::from(highlight_matches(&row, &query, Style::default().fg(theme.muted), theme)));
                 }
 
+                let height = item_lines.len() as u16;
+                let item_area = Rect::new(inner.x, next_row, inner.width, height).intersection(inner);
+                app.hits.borrow_mut().push((item_area, Hit::FeedItem(feed_idx, item_idx)));
+                next_row = next_row.saturating_add(height + 1);
                 items.push(ListItem::new(item_lines));
                 
                 if i < count - 1 {
                     items.push(ListItem::new(Line::from(vec![
-                        Span::styled("─".repeat(inner_width), Style::default().fg(BORDER_MUTED))
+                        Span::styled("─".repeat(inner_width), Style::default().fg(theme.border))
                     ])));
                 }
             }
-        } else {
-            items.push(ListItem::new("   Fetching data..."));
+            if app.loading_more[feed_idx] {
+                items.push(ListItem::new(Span::styled("   Loading more...", Style::default().fg(theme.muted).italic())));
+            } else if app.focused_feed() == Some(feed_idx) && app.at_feed_end(feed_idx) && app.rss_feeds[feed_idx].next_page.is_some() {
+                items.push(ListItem::new(Span::styled("   [M] load more", Style::default().fg(theme.muted))));
+            }
+        } else if !app.rss_feeds[feed_idx].items.is_empty() {
+            items.push(ListItem::new(Span::styled("   No matches", Style::default().fg(theme.muted).italic())));
+        } else if app.fetch_state[feed_idx] != FetchState::Error && app.rate_limited_until[feed_idx].is_none() {
+            items.push(ListItem::new(format!("   {} Fetching data...", app.spinner())));
+        }
+
+        if let Some(until) = app.rate_limited_until[feed_idx] {
+            let secs = (until - app.now()).num_seconds().max(0);
+            items.push(ListItem::new(Span::styled(
+                format!("   ⏳ rate-limited, retrying in {}s", secs),
+                Style::default().fg(theme.highlight),
+            )));
+        }
+
+        if let Some(err) = &app.errors[feed_idx]
+            && app.config.error_display != ErrorDisplay::Compact
+        {
+            // A stale feed still shows its items, so the message gets one line there
+            let rows_left = (area.height as usize).saturating_sub(2);
+            let max_rows = if feed.is_empty() { rows_left.max(1) } else { 1 };
+            for row in wrap_clipped(&format!("⚠ fetch failed: {}", err), inner_width.saturating_sub(3), max_rows) {
+                items.push(ListItem::new(Span::styled(format!("   {}", row), Style::default().fg(theme.error))));
+            }
+        }
+    }
+    let mut title = title.to_string();
+    // Items already on screen stay put while a refresh runs; the title shows it's in flight
+    if app.fetch_state[feed_idx] == FetchState::Loading && !app.rss_feeds[feed_idx].items.is_empty() {
+        title.push_str(&format!("{} ", app.spinner()));
+    }
+    if app.stale[feed_idx] {
+        title.push_str("(stale) ");
+    }
+    if app.errors[feed_idx].is_some() && app.config.error_display != ErrorDisplay::Verbose {
+        title.push_str("(err) ");
+    }
+    let mut block = create_block(title, color, theme);
+    if let Some(at) = app.fetched_at[feed_idx] {
+        let updated = format!(" updated {} ", updated_ago(at, app.now()));
+        block = block.title_bottom(Line::from(Span::styled(updated, Style::default().fg(theme.muted).italic())).right_aligned());
+    }
+    // On the border so it shows however few rows the block has
+    if app.offline[feed_idx] {
+        block = block.title_bottom(Line::from(Span::styled(" showing cached (offline) ", Style::default().fg(theme.highlight).italic())));
+    }
+    if app.focused_feed() == Some(feed_idx) {
+        block = block.border_style(Style::default().fg(color));
+    }
+    frame.render_widget(List::new(items).block(block), area);
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveTime, TimeZone};
+    use config::QuietHours;
+
+    // Fixed clocks for App::with_clock: midday and late evening on the same day
+    fn noon() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2026, 3, 2, 12, 0, 0).unwrap()
+    }
+
+    fn late_evening() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2026, 3, 2, 23, 30, 0).unwrap()
+    }
+
+    fn test_app(config: Config, clock: Clock) -> App {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let (_tg_tx, tg_rx) = mpsc::unbounded_channel();
+        App::with_clock(tx, rx, tg_rx, Shutdown::default(), UiState::default(), config, Theme::default(), None, None, clock)
+    }
+
+    fn overnight_quiet() -> Config {
+        let time = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+        Config { quiet_hours: Some(QuietHours { start: time(22, 0), end: time(7, 0) }), ..Config::default() }
+    }
+
+    fn footer_text(app: &App) -> String {
+        footer_segments(app, 0.0).iter().flat_map(|seg| seg.spans.iter()).map(|span| span.content.to_string()).collect()
+    }
+
+    #[test]
+    fn quiet_hours_follow_the_clock() {
+        let app = test_app(overnight_quiet(), noon);
+        assert!(!app.is_quiet());
+        assert!(!footer_text(&app).contains("quiet"));
+
+        let app = test_app(overnight_quiet(), late_evening);
+        assert!(app.is_quiet());
+        assert!(footer_text(&app).contains("☾ quiet"));
+    }
+
+    #[test]
+    fn ages_are_relative_to_the_clock() {
+        let app = test_app(Config::default(), noon);
+        assert_eq!(updated_ago(noon() - chrono::Duration::seconds(42), app.now()), "42s ago");
+        assert_eq!(updated_ago(noon() - chrono::Duration::minutes(5), app.now()), "5m ago");
+        let sent = (noon() - chrono::Duration::hours(3)).with_timezone(&Utc);
+        assert_eq!(message_age(sent, app.now(), None), "3h ago");
+    }
+
+    // Foreground of the first cell where `text` is drawn
+    fn fg_of(buffer: &ratatui::buffer::Buffer, text: &str) -> Option<Color> {
+        let area = buffer.area;
+        (area.top()..area.bottom()).find_map(|y| {
+            let row: String = (area.left()..area.right()).map(|x| buffer[(x, y)].symbol()).collect();
+            let col = row.find(text)?;
+            let x = row[..col].chars().count() as u16;
+            Some(buffer[(area.left() + x, y)].fg)
+        })
+    }
+
+    #[test]
+    fn feed_colours_reach_the_block_titles() {
+        let mut config = Config::default();
+        config.feeds[0].color = Some(Color::Rgb(1, 2, 3));
+        config.feeds[1].color = None;
+        let (own, fallback) = (config.feeds[0].title.trim().to_string(), config.feeds[1].title.trim().to_string());
+        let mut app = test_app(config, noon);
+        app.theme.feed_colors = vec![Color::Rgb(9, 9, 9), Color::Rgb(4, 5, 6)].into();
+
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(160, 40)).unwrap();
+        terminal.draw(|frame| render_grid(frame, frame.area(), &app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        assert_eq!(fg_of(buffer, &own), Some(Color::Rgb(1, 2, 3)));
+        // The second feed in the config takes the second palette colour
+        assert_eq!(fg_of(buffer, &fallback), Some(Color::Rgb(4, 5, 6)));
+    }
+
+    #[test]
+    fn spinner_follows_elapsed_time() {
+        let mut app = test_app(Config::default(), noon);
+        app.spinner_started = Instant::now() - Duration::from_millis(250);
+        assert_eq!(app.spinner(), SPINNER[2]);
+        app.spinner_started = Instant::now() - SPINNER_FRAME * 12;
+        assert_eq!(app.spinner(), SPINNER[2], "wraps round after the last frame");
+        app.paused = true;
+        assert_eq!(app.spinner(), SPINNER[0]);
+
+        let mut app = test_app(overnight_quiet(), late_evening);
+        app.spinner_started = Instant::now() - Duration::from_millis(250);
+        assert_eq!(app.spinner(), SPINNER[0], "frozen during quiet hours");
+    }
+
+    fn fetched(titles: &[&str]) -> Result<Fetched, FetchError> {
+        Ok(Fetched::Modified(Feed { items: titles.iter().map(|t| item(t)).collect(), ..Feed::default() }, Validators::default()))
+    }
+
+    #[test]
+    fn a_shrunken_refresh_keeps_the_last_items_once() {
+        let mut app = test_app(Config::default(), noon);
+        let full = ["a", "b", "c", "d", "e", "f"];
+        app.apply_feed(0, fetched(&full));
+        app.apply_feed(0, fetched(&["a"]));
+        assert_eq!(app.rss_feeds[0].items.len(), full.len());
+        assert!(app.stale[0]);
+        // The feed really did get smaller
+        app.apply_feed(0, fetched(&["a"]));
+        assert_eq!(app.rss_feeds[0].items.len(), 1);
+        assert!(!app.stale[0]);
+    }
+
+    #[test]
+    fn replace_takes_a_shrunken_refresh_as_is() {
+        let config = Config { empty_policy: EmptyPolicy::Replace, ..Config::default() };
+        let mut app = test_app(config, noon);
+        app.apply_feed(0, fetched(&["a", "b", "c", "d", "e", "f"]));
+        app.apply_feed(0, fetched(&["a"]));
+        assert_eq!(app.rss_feeds[0].items.len(), 1);
+        assert!(!app.stale[0]);
+    }
+
+    #[test]
+    fn wrap_title_splits_at_words_and_carries_long_ones_on() {
+        assert_eq!(wrap_title("Short title", 20, 20), ("Short title".to_string(), None));
+        let title = "Markets rally as rates hold steady";
+        let (first, rest) = wrap_title(title, 16, 40);
+        assert!(first.width() <= 16 && !first.ends_with(' '), "{:?}", first);
+        assert_eq!(format!("{} {}", first, rest.unwrap()), title);
+        // One word wider than the row is hard-split, and the second row picks up where the first stopped
+        let (first, rest) = wrap_title("Supercalifragilisticexpialidocious", 10, 40);
+        assert_eq!(first, "Supercalif");
+        assert_eq!(rest.as_deref(), Some("ragilisticexpialidocious"));
+        let (_, rest) = wrap_title("Supercalifragilisticexpialidocious", 10, 12);
+        assert_eq!(rest.as_deref(), Some("ragilisti..."));
+    }
+
+    #[test]
+    fn tiny_terminals_get_the_too_small_notice() {
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(30, 10)).unwrap();
+        terminal.draw(|frame| render_too_small(frame, frame.area(), &Theme::default())).unwrap();
+        let buffer = terminal.backend().buffer();
+        let text: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("Terminal too small"), "{}", text);
+        assert!(text.contains(&format!("30×10, needs {}×{}", MIN_COLUMNS, MIN_ROWS)), "{}", text);
+    }
+
+    #[test]
+    fn truncation_counts_wide_characters_and_keeps_graphemes_whole() {
+        let title = "東京の天気予報 🌧️ 雨のち晴れ 👨‍👩‍👧 家族";
+        for width in 4..title.width() {
+            let cut = truncate_to_width(title, width);
+            assert!(cut.width() <= width, "{:?} is wider than {}", cut, width);
+            let kept = cut.strip_suffix("...").unwrap();
+            assert!(title.starts_with(kept), "{:?}", cut);
+            // Ends on a grapheme boundary of the original
+            assert!(title.grapheme_indices(true).any(|(i, _)| i == kept.len()), "{:?} splits a grapheme", cut);
+        }
+        assert_eq!(truncate_to_width(title, title.width()), title);
+        for width in 0..3 {
+            assert_eq!(truncate_to_width(title, width), ".".repeat(width));
+        }
+        assert_eq!(truncate_to_width("ab", 2), "ab");
+    }
+
+    #[test]
+    fn wrap_text_breaks_paragraphs_at_words() {
+        let paragraph = "The quick brown fox jumps over the lazy dog while the cat watches";
+        let rows = wrap_text(paragraph, 20);
+        assert!(rows.len() > 1 && rows.iter().all(|row| row.width() <= 20), "{:?}", rows);
+        assert_eq!(rows.join(" "), paragraph, "no word is split");
+    }
+
+    #[test]
+    fn wrap_text_hard_splits_long_urls_to_the_width() {
+        let url = "https://example.com/articles/2026/03/02/a-very-long-slug-for-an-article";
+        let rows = wrap_text(&format!("Read more: {} today", url), 24);
+        assert!(rows.iter().all(|row| row.width() <= 24), "{:?}", rows);
+        assert_eq!(rows.concat().replace(' ', ""), format!("Readmore:{}today", url));
+    }
+
+    // Row `y` as text, skipping the cell hidden behind each wide character
+    fn row_text(buffer: &ratatui::buffer::Buffer, y: u16) -> String {
+        let mut text = String::new();
+        let mut x = buffer.area.left();
+        while x < buffer.area.right() {
+            let symbol = buffer[(x, y)].symbol();
+            text.push_str(symbol);
+            x += symbol.width().max(1) as u16;
         }
+        text
+    }
+
+    #[test]
+    fn wide_titles_and_tags_keep_the_date_aligned() {
+        let mut config = Config::default();
+        config.feeds[0].category = Some("経済".to_string());
+        let mut app = test_app(config, noon);
+        let published = Some(noon().with_timezone(&Utc));
+        app.rss_feeds[0].items = vec![
+            FeedItem { title: "東京株価が上昇".to_string(), published, ..item("tokyo") },
+            FeedItem { title: "Markets rally".to_string(), published, ..item("markets") },
+        ];
+        let date = app.rss_feeds[0].items[0].short_date(None);
+
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(160, 40)).unwrap();
+        terminal.draw(|frame| render_grid(frame, frame.area(), &app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let date_column = |title: &str| {
+            let row = (0..buffer.area.height).map(|y| row_text(buffer, y)).find(|row| row.contains(title)).unwrap();
+            assert!(row.contains(&format!("{} [経済] │", date)), "{}", row);
+            row[..row.find(&date).unwrap()].width()
+        };
+        // Padding is counted in columns, so wide titles push the date no further right than narrow ones
+        assert_eq!(date_column("東京株価が上昇"), date_column("Markets rally"));
+    }
+
+    #[test]
+    fn clipped_rows_leave_room_for_the_ellipsis() {
+        // One long token fills every row to the edge
+        let message = "x".repeat(70);
+        let rows = wrap_clipped(&message, 20, 2);
+        assert_eq!(rows.len(), 2);
+        assert!(rows[1].ends_with('…'), "{:?}", rows);
+        assert!(rows.iter().all(|row| row.width() <= 20), "{:?}", rows);
+        assert_eq!(wrap_clipped("short", 20, 1), ["short"]);
+    }
+
+    #[test]
+    fn highlights_ignore_case_throughout_the_match() {
+        let theme = Theme::default();
+        let hits = |text: &str, query: &str| -> Vec<String> {
+            highlight_matches(text, query, Style::default(), &theme)
+                .into_iter()
+                .filter(|span| span.style.bg == Some(theme.highlight))
+                .map(|span| span.content.into_owned())
+                .collect()
+        };
+        assert_eq!(hits("Rust and RUST and rUsT", "RuSt"), ["Rust", "RUST", "rUsT"]);
+        assert_eq!(hits("İstanbul news", "i̇stan"), ["İstan"]);
+        let spans = highlight_matches("Say RUST twice", "rust", Style::default(), &theme);
+        assert_eq!(spans.iter().map(|span| span.content.as_ref()).collect::<String>(), "Say RUST twice");
+    }
+
+    #[test]
+    fn seen_keys_follow_the_latest_response() {
+        let mut app = test_app(Config::default(), noon);
+        app.apply_feed(0, fetched(&["a", "b", "c", "d"]));
+        app.apply_feed(0, fetched(&["e", "b", "c", "d"]));
+        let keys = |app: &App| {
+            let mut keys: Vec<String> = app.seen[0].iter().cloned().collect();
+            keys.sort();
+            keys
+        };
+        assert_eq!(keys(&app), ["https://example.com/b", "https://example.com/c", "https://example.com/d", "https://example.com/e"]);
+        assert_eq!(app.new_items[0], HashSet::from(["https://example.com/e".to_string()]));
+    }
+
+    #[test]
+    fn only_network_errors_fall_back_to_the_offline_copy() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = test_app(Config::default(), noon);
+        app.cache = Some(FeedCache::open(dir.path().to_path_buf()));
+        app.apply_feed(0, fetched(&["a", "b"]));
+
+        // A fresh app, as after a restart with the host down
+        let mut app = test_app(Config::default(), noon);
+        app.cache = Some(FeedCache::open(dir.path().to_path_buf()));
+        app.apply_feed(0, Err(FetchError::Parse("bad XML".to_string())));
+        assert!(app.rss_feeds[0].items.is_empty() && !app.offline[0]);
+        app.apply_feed(0, Err(FetchError::Network("could not connect".to_string())));
+        assert_eq!(app.rss_feeds[0].items.len(), 2);
+        assert!(app.offline[0]);
+        assert_eq!(app.fetched_at[0], Some(noon()));
+    }
+
+    #[test]
+    fn a_saved_offset_past_a_shorter_feed_is_clamped() {
+        let mut app = test_app(Config::default(), noon);
+        let url = app.config.feeds[0].url.clone();
+        app.ui_state.offsets.insert(url, 9);
+        app.apply_feed(0, fetched(&["a", "b", "c"]));
+        assert_eq!(app.offsets[0], 2);
+        // Only the first load picks the saved place back up
+        app.offsets[0] = 0;
+        app.apply_feed(0, fetched(&["a", "b", "c", "d"]));
+        assert_eq!(app.offsets[0], 0);
+    }
+
+    #[test]
+    fn restoring_drops_feeds_that_were_removed() {
+        let mut app = test_app(Config::default(), noon);
+        app.ui_state.focused_feed = Some("https://gone.example/rss".to_string());
+        app.ui_state.view = SavedView::Focused("https://gone.example/rss".to_string());
+        app.restore_ui_state();
+        assert_eq!(app.focused, None);
+        assert_eq!(app.view, ViewMode::Grid);
+
+        let url = app.config.feeds[1].url.clone();
+        app.ui_state.focused_feed = Some(url.clone());
+        app.ui_state.view = SavedView::Focused(url);
+        app.restore_ui_state();
+        assert_eq!(app.focused, Some(Panel::Feed(1)));
+        assert_eq!(app.view, ViewMode::Focused(1));
+    }
+
+    #[test]
+    fn a_restored_timeline_selection_is_clamped() {
+        let mut app = test_app(Config::default(), noon);
+        app.ui_state.view = SavedView::Timeline;
+        app.ui_state.timeline_selected = 40;
+        app.restore_ui_state();
+        app.apply_feed(0, fetched(&["a", "b"]));
+        assert_eq!(app.focused_item().map(|(_, item)| item.title.as_str()), Some("b"));
+        app.clamp_to_view();
+        assert_eq!(app.timeline_selected, 1);
     }
-    frame.render_widget(List::new(items).block(create_block(title, color)), area);
-}
\ No newline at end of file
+
+    fn item(title: &str) -> FeedItem {
+        FeedItem { title: title.to_string(), link: format!("https://example.com/{}", title), ..FeedItem::default() }
+    }
+
+    #[test]
+    fn selecting_an_item_marks_it_read() {
+        let mut app = test_app(Config::default(), noon);
+        app.rss_feeds[0].items = vec![item("first"), item("second")];
+        app.mark_selected_read();
+        assert!(app.read.is_empty(), "nothing is selected without focus");
+
+        app.focused = Some(Panel::Feed(0));
+        app.offsets[0] = 1;
+        app.mark_selected_read();
+        assert_eq!(app.read, HashSet::from(["https://example.com/second".to_string()]));
+    }
+
+    #[test]
+    fn auto_focus_does_not_mark_read() {
+        let mut app = test_app(Config::default(), noon);
+        app.rss_feeds[0].items = vec![item("first")];
+        app.focused = Some(Panel::Feed(0));
+        app.auto_focus_until = Some(noon() + chrono::Duration::seconds(10));
+        app.mark_selected_read();
+        assert!(app.read.is_empty());
+    }
+}
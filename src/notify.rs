@@ -0,0 +1,24 @@
+use notify_rust::Notification;
+
+/// Popups per feed per refresh; anything past this is summed up in one more.
+const MAX_PER_REFRESH: usize = 3;
+
+/// One desktop notification per new headline, titled with the feed's name.
+/// Runs on a blocking thread since talking to the notification daemon can stall.
+pub fn new_items(feed_title: &str, headlines: Vec<String>) {
+    let feed_title = feed_title.trim().to_string();
+    tokio::task::spawn_blocking(move || {
+        for headline in headlines.iter().take(MAX_PER_REFRESH) {
+            show(&feed_title, headline);
+        }
+        let rest = headlines.len().saturating_sub(MAX_PER_REFRESH);
+        if rest > 0 {
+            show(&feed_title, &format!("…and {} more new items", rest));
+        }
+    });
+}
+
+fn show(summary: &str, body: &str) {
+    // No notification daemon (e.g. over SSH) just means no popups
+    let _ = Notification::new().appname("rss-tui").summary(summary).body(body).show();
+}
@@ -0,0 +1,140 @@
+use crate::config::{self, FeedConfig};
+use opml::{Head, Outline, OPML};
+use std::fs;
+use std::path::Path;
+
+/// Reads an OPML subscription list. Feeds fill the `columns` x `slots` grid column by column in
+/// document order, and a feed nested under a group outline takes that group's name as its category.
+pub fn import(path: &Path, columns: usize, slots: usize) -> anyhow::Result<Vec<FeedConfig>> {
+    let xml = fs::read_to_string(path)?;
+    let document = OPML::from_str(&xml).map_err(|e| anyhow::anyhow!("Invalid OPML {}: {}", path.display(), e))?;
+
+    let mut feeds = Vec::new();
+    collect(&document.body.outlines, None, &mut feeds);
+
+    let listed = feeds.len();
+    if config::place_in_grid(&mut feeds, columns, slots) > 0 {
+        eprintln!(
+            "warning: {} lists {} feeds but only {} fit on screen; the rest are skipped",
+            path.display(), listed, feeds.len()
+        );
+    }
+    Ok(feeds)
+}
+
+/// Writes the feeds as OPML 2.0, grouping categorised feeds under one outline per category.
+pub fn export(feeds: &[FeedConfig], path: &Path) -> anyhow::Result<()> {
+    let mut document = OPML {
+        head: Some(Head { title: Some("rss-tui feeds".to_string()), ..Head::default() }),
+        ..OPML::default()
+    };
+    for feed in feeds {
+        let outline = Outline {
+            text: feed.title.trim().to_string(),
+            title: Some(feed.title.trim().to_string()),
+            r#type: Some("rss".to_string()),
+            xml_url: Some(feed.url.clone()),
+            ..Outline::default()
+        };
+        let Some(category) = &feed.category else {
+            document.body.outlines.push(outline);
+            continue;
+        };
+        let outlines = &mut document.body.outlines;
+        let group = match outlines.iter().position(|o| o.xml_url.is_none() && o.text == *category) {
+            Some(i) => &mut outlines[i],
+            None => {
+                outlines.push(Outline { text: category.clone(), title: Some(category.clone()), ..Outline::default() });
+                outlines.last_mut().expect("just pushed")
+            }
+        };
+        group.outlines.push(outline);
+    }
+    let xml = document.to_string().map_err(|e| anyhow::anyhow!("Could not build OPML: {}", e))?;
+    fs::write(path, format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n{}\n", xml))?;
+    Ok(())
+}
+
+fn collect(outlines: &[Outline], category: Option<&str>, feeds: &mut Vec<FeedConfig>) {
+    for outline in outlines {
+        let name = if outline.text.trim().is_empty() {
+            outline.title.as_deref().unwrap_or_default().trim()
+        } else {
+            outline.text.trim()
+        };
+        match &outline.xml_url {
+            Some(url) => feeds.push(FeedConfig {
+                url: url.clone(),
+                title: format!(" {} ", if name.is_empty() { url } else { name }),
+                color: None,
+                category: category.map(str::to_string),
+                column: 0,
+                slot: 0,
+                notify: false,
+            }),
+            // Anything without a feed URL is a folder
+            None => collect(&outline.outlines, Some(name).filter(|n| !n.is_empty()).or(category), feeds),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUBSCRIPTIONS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<opml version="2.0">
+  <head><title>Subscriptions</title></head>
+  <body>
+    <outline text="Loose" type="rss" xmlUrl="https://loose.example/rss"/>
+    <outline text="Tech">
+      <outline text="Hacker News" type="rss" xmlUrl="https://hn.example/rss"/>
+      <outline title="Titled only" type="rss" xmlUrl="https://titled.example/rss"/>
+    </outline>
+    <outline text="Money">
+      <outline text="Stocks" type="rss" xmlUrl="https://stocks.example/rss"/>
+    </outline>
+  </body>
+</opml>"#;
+
+    fn import_str(xml: &str, columns: usize, slots: usize) -> Vec<FeedConfig> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("subscriptions.opml");
+        fs::write(&path, xml).unwrap();
+        import(&path, columns, slots).unwrap()
+    }
+
+    // Feeds as (url, title, category), the parts OPML carries
+    fn summary(feeds: &[FeedConfig]) -> Vec<(String, String, Option<String>)> {
+        let mut summary: Vec<_> = feeds.iter().map(|f| (f.url.clone(), f.title.clone(), f.category.clone())).collect();
+        summary.sort();
+        summary
+    }
+
+    #[test]
+    fn groups_become_categories() {
+        let feeds = import_str(SUBSCRIPTIONS, 2, 2);
+        let placed: Vec<_> = feeds.iter().map(|f| (f.url.as_str(), f.category.as_deref(), f.column, f.slot)).collect();
+        assert_eq!(
+            placed,
+            [
+                ("https://loose.example/rss", None, 0, 0),
+                ("https://hn.example/rss", Some("Tech"), 0, 1),
+                ("https://titled.example/rss", Some("Tech"), 1, 0),
+                ("https://stocks.example/rss", Some("Money"), 1, 1),
+            ]
+        );
+        assert_eq!(feeds[2].title, " Titled only ");
+        // Whatever doesn't fit the grid is left out
+        assert_eq!(import_str(SUBSCRIPTIONS, 1, 3).len(), 3);
+    }
+
+    #[test]
+    fn export_then_import_gives_the_same_feeds() {
+        let feeds = import_str(SUBSCRIPTIONS, 2, 2);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("feeds.opml");
+        export(&feeds, &path).unwrap();
+        assert_eq!(summary(&import(&path, 2, 2).unwrap()), summary(&feeds));
+    }
+}
@@ -1,30 +1,1270 @@
+use chrono::{DateTime, Local, Utc};
+use chrono_tz::Tz;
+use encoding_rs::{Encoding, UTF_8};
+use reqwest::header::{CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, RETRY_AFTER};
+use reqwest::redirect::Policy;
+use reqwest::{Client, Proxy, StatusCode};
 use rss::Channel;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
 use std::error::Error;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-pub async fn get_feed(url: &str) -> Result<Vec<(String, String, String)>, Box<dyn Error + Send + Sync>> {
-    let content = reqwest::get(url).await?.bytes().await?;
-    let channel = Channel::read_from(&content[..])?;
-    
+/// Give up on a host that hasn't accepted the connection by then
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Whole request, including reading the body
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Extra attempts after a network error or 5xx, doubling the wait from `RETRY_BASE_DELAY` each time
+pub const MAX_RETRIES: u32 = 2;
+pub const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+/// Hops allowed before a fetch gives up; anything longer is most likely a loop
+pub const MAX_REDIRECTS: usize = 5;
+/// Cap on a downloaded item image, so a huge enclosure can't eat memory
+pub const MAX_IMAGE_BYTES: usize = 5 * 1024 * 1024;
+/// Some hosts answer 403/429 to requests without a User-Agent
+pub const DEFAULT_USER_AGENT: &str = concat!("rss-tui/", env!("CARGO_PKG_VERSION"));
+
+/// Which proxy a feed's requests go through.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ProxyChoice {
+    /// reqwest's default: honour HTTP_PROXY/HTTPS_PROXY from the environment
+    System,
+    /// Bypass every proxy
+    Direct,
+    Via(String),
+}
+
+/// One lazily built `reqwest::Client` per proxy setting, shared by all feeds that use it.
+pub struct ClientPool {
+    clients: Mutex<HashMap<ProxyChoice, Client>>,
+    connect_timeout: Duration,
+    timeout: Duration,
+    user_agent: String,
+}
+
+impl ClientPool {
+    pub fn new(connect_timeout: Duration, timeout: Duration, user_agent: String) -> Self {
+        Self {
+            clients: Mutex::new(HashMap::new()),
+            connect_timeout,
+            timeout,
+            user_agent,
+        }
+    }
+
+    pub fn get(&self, choice: &ProxyChoice) -> Result<Client, reqwest::Error> {
+        let mut clients = self.clients.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(client) = clients.get(choice) {
+            return Ok(client.clone());
+        }
+        let builder = match choice {
+            ProxyChoice::System => Client::builder(),
+            ProxyChoice::Direct => Client::builder().no_proxy(),
+            ProxyChoice::Via(url) => Client::builder().proxy(Proxy::all(url)?),
+        };
+        let client = builder
+            .connect_timeout(self.connect_timeout)
+            .timeout(self.timeout)
+            .user_agent(self.user_agent.as_str())
+            .redirect(Policy::limited(MAX_REDIRECTS))
+            .build()?;
+        clients.insert(choice.clone(), client.clone());
+        Ok(client)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Feed {
+    /// The feed's own name, e.g. the site title (may be empty)
+    pub title: String,
+    /// Site homepage advertised by the channel (may be empty)
+    pub link: String,
+    pub items: Vec<FeedItem>,
+    /// Older page advertised via `<atom:link rel="next">`, if the feed paginates
+    pub next_page: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FeedItem {
+    pub title: String,
+    /// `None` when the feed leaves the date out or uses a format we can't read
+    pub published: Option<DateTime<Utc>>,
+    /// Short text for the list views: `<description>` (RSS) or `<summary>` (Atom)
+    pub summary: String,
+    /// The whole article from `<content:encoded>` (RSS) or `<content>` (Atom), when the feed carries it
+    pub full_content: Option<String>,
+    /// Article URL; empty when the feed doesn't give one
+    pub link: String,
+    /// Stable id from `<guid>` (RSS) or `<id>` (Atom), when the feed provides one
+    pub guid: Option<String>,
+    /// `<category>` names (RSS) or category labels/terms (Atom), as the feed wrote them
+    pub categories: Vec<String>,
+    /// First image found in the enclosure, Media RSS elements or an `<img>` in the text
+    pub image: Option<String>,
+}
+
+impl FeedItem {
+    /// What the reading pane shows: the full article if there is one, else the summary.
+    pub fn body(&self) -> &str {
+        self.full_content.as_deref().unwrap_or(&self.summary)
+    }
+
+    /// "Jan 02" in `tz` (local time when `None`), or "N/A" without a usable date.
+    pub fn short_date(&self, tz: Option<Tz>) -> String {
+        self.format_published(tz, "%b %d")
+    }
+
+    /// e.g. "Jan 02 15:04", for the merged timeline where items from one day need telling apart.
+    pub fn date_time(&self, tz: Option<Tz>) -> String {
+        self.format_published(tz, "%b %d %H:%M")
+    }
+
+    /// e.g. "Tue 02 Jan 2024 15:04", for the reading pane.
+    pub fn full_date(&self, tz: Option<Tz>) -> String {
+        self.format_published(tz, "%a %d %b %Y %H:%M")
+    }
+
+    fn format_published(&self, tz: Option<Tz>, format: &str) -> String {
+        format_date(self.published, tz, format)
+    }
+
+    /// Identifies the entry across refreshes: its guid, else its link, else its title.
+    pub fn key(&self) -> &str {
+        match &self.guid {
+            Some(guid) => guid,
+            None if !self.link.is_empty() => &self.link,
+            None => &self.title,
+        }
+    }
+}
+
+/// `at` in `tz` (local time when `None`) using a chrono `format`, or "N/A" without a date.
+pub fn format_date(at: Option<DateTime<Utc>>, tz: Option<Tz>, format: &str) -> String {
+    match (at, tz) {
+        (None, _) => "N/A".to_string(),
+        (Some(at), Some(tz)) => at.with_timezone(&tz).format(format).to_string(),
+        (Some(at), None) => at.with_timezone(&Local).format(format).to_string(),
+    }
+}
+
+/// Which syndication format a payload is, judged by its root element (or a leading `{` for JSON).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FeedFormat {
+    Rss,
+    Atom,
+    Json,
+}
+
+/// Cache validators from a previous response, replayed so an unchanged feed costs a 304.
+#[derive(Debug, Clone, Default)]
+pub struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+pub enum Fetched {
+    Modified(Feed, Validators),
+    /// The server confirmed the copy we already have is current
+    NotModified,
+}
+
+/// Why a fetch failed, so the block can say whether the host, the server or the payload is at fault.
+#[derive(Debug)]
+pub enum FetchError {
+    /// No usable response: DNS, connect, TLS, timeout or a dropped body
+    Network(String),
+    /// The server answered with a non-success status
+    Status(StatusCode),
+    /// The body isn't RSS or Atom we can read
+    Parse(String),
+    /// 429 Too Many Requests, with how long `Retry-After` asked us to wait if it said
+    RateLimited(Option<Duration>),
+    /// The feed's configured proxy URL is unusable
+    Proxy(String),
+    /// More than `MAX_REDIRECTS` hops; holds the last URL reached
+    TooManyRedirects(Option<String>),
+    /// The response is a web page (a block page, login or "moved" notice), not a feed
+    NotAFeed { url: String, redirected: bool },
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Network(msg) => write!(f, "network error: {}", msg),
+            FetchError::Status(status) => write!(f, "HTTP {}", status),
+            FetchError::Parse(msg) => write!(f, "parse error: {}", msg),
+            FetchError::RateLimited(_) => write!(f, "rate-limited"),
+            FetchError::Proxy(msg) => write!(f, "bad proxy: {}", msg),
+            FetchError::TooManyRedirects(Some(url)) => {
+                write!(f, "more than {} redirects (a redirect loop?), last at {}", MAX_REDIRECTS, url)
+            }
+            FetchError::TooManyRedirects(None) => write!(f, "more than {} redirects (a redirect loop?)", MAX_REDIRECTS),
+            FetchError::NotAFeed { url, redirected: true } => {
+                write!(f, "expected a feed but was redirected to an HTML page at {}; has it moved or does it need a login?", url)
+            }
+            FetchError::NotAFeed { .. } => write!(f, "expected a feed but got HTML (the site may be blocking requests)"),
+        }
+    }
+}
+
+impl Error for FetchError {}
+
+impl FetchError {
+    /// Worth another try: the host or the server may recover, a 4xx or bad payload won't.
+    fn is_transient(&self) -> bool {
+        match self {
+            FetchError::Network(_) => true,
+            FetchError::Status(status) => status.is_server_error(),
+            // The caller waits out a rate limit; hammering the host again straight away won't help
+            FetchError::Parse(_)
+            | FetchError::RateLimited(_)
+            | FetchError::Proxy(_)
+            | FetchError::TooManyRedirects(_)
+            | FetchError::NotAFeed { .. } => false,
+        }
+    }
+}
+
+impl From<reqwest::Error> for FetchError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_redirect() {
+            return FetchError::TooManyRedirects(e.url().map(|url| url.to_string()));
+        }
+        let msg = if e.is_timeout() {
+            "timed out".to_string()
+        } else if e.is_connect() {
+            // The underlying cause (DNS, refused, TLS) is more useful than reqwest's wrapper
+            e.source().and_then(|s| s.source()).map_or_else(|| "could not connect".to_string(), |s| s.to_string())
+        } else {
+            e.without_url().to_string()
+        };
+        FetchError::Network(msg)
+    }
+}
+
+/// One conditional GET of `url` through `client`, parsed into a `Feed` unless the server says the
+/// copy behind `validators` is still current. A `ClientPool` builds suitable clients.
+///
+/// ```no_run
+/// use rss_tui::rss_funcs::{get_feed, ClientPool, Fetched, ProxyChoice, Validators};
+/// use std::time::Duration;
+///
+/// # async fn run() -> Result<(), rss_tui::FetchError> {
+/// let pool = ClientPool::new(Duration::from_secs(5), Duration::from_secs(10), "my-app/1.0".to_string());
+/// let client = pool.get(&ProxyChoice::System)?;
+/// if let Fetched::Modified(feed, validators) = get_feed(&client, "https://example.com/feed.xml", &Validators::default()).await? {
+///     println!("{} items; ETag {:?}", feed.items.len(), validators.etag);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn get_feed(client: &Client, url: &str, validators: &Validators) -> Result<Fetched, FetchError> {
+    let mut request = client.get(url);
+    if let Some(etag) = &validators.etag {
+        request = request.header(IF_NONE_MATCH, etag);
+    }
+    if let Some(date) = &validators.last_modified {
+        request = request.header(IF_MODIFIED_SINCE, date);
+    }
+    let response = request.send().await?;
+    if response.status() == StatusCode::NOT_MODIFIED {
+        return Ok(Fetched::NotModified);
+    }
+    if response.status() == StatusCode::TOO_MANY_REQUESTS {
+        let wait = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| parse_retry_after(v, Utc::now()));
+        return Err(FetchError::RateLimited(wait));
+    }
+    if !response.status().is_success() {
+        return Err(FetchError::Status(response.status()));
+    }
+    let header = |name| response.headers().get(name).and_then(|v| v.to_str().ok()).map(str::to_string);
+    let validators = Validators {
+        etag: header(ETAG),
+        last_modified: header(LAST_MODIFIED),
+    };
+    let content_type = header(CONTENT_TYPE);
+    let redirected = reqwest::Url::parse(url).ok().as_ref() != Some(response.url());
+    let final_url = response.url().to_string();
+    let content = response.bytes().await?;
+    // Some servers label real feeds text/html, so only a body that isn't RSS or Atom counts against it.
+    // Without a usable Content-Type the body itself has to look like a web page.
+    let media_type = content_type
+        .as_deref()
+        .map(|ct| ct.split(';').next().unwrap_or_default().trim().to_ascii_lowercase())
+        .unwrap_or_default();
+    let html_type = media_type == "text/html" || media_type == "application/xhtml+xml";
+    if detect_format(&content).is_none() && (html_type || looks_like_html(&content)) {
+        return Err(FetchError::NotAFeed { url: final_url, redirected });
+    }
+    let json_type = media_type == "application/feed+json" || media_type == "application/json";
+    let content = to_utf8(&content, content_type.as_deref());
+    let feed = parse_document(&content, json_type).map_err(|e| FetchError::Parse(e.to_string()))?;
+    Ok(Fetched::Modified(feed, validators))
+}
+
+/// Downloads an item's image for the reading pane, giving up past `MAX_IMAGE_BYTES`.
+pub async fn get_image(client: &Client, url: &str) -> Result<Vec<u8>, FetchError> {
+    let mut response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        return Err(FetchError::Status(response.status()));
+    }
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        bytes.extend_from_slice(&chunk);
+        if bytes.len() > MAX_IMAGE_BYTES {
+            return Err(FetchError::Parse(format!("image larger than {} bytes", MAX_IMAGE_BYTES)));
+        }
+    }
+    Ok(bytes)
+}
+
+/// `Retry-After` as a wait from `now`: either delay-seconds or an HTTP-date (a date already past means no wait).
+pub fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc);
+    Some((at - now).to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Fetches and parses `url` with the default timeouts, user agent and system proxy, retrying
+/// transient failures like the TUI does.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), rss_tui::FetchError> {
+/// let feed = rss_tui::fetch_feed("https://example.com/feed.json").await?;
+/// println!("{}: {} items", feed.title, feed.items.len());
+/// # Ok(())
+/// # }
+/// ```
+pub async fn fetch_feed(url: &str) -> Result<Feed, FetchError> {
+    let pool = ClientPool::new(DEFAULT_CONNECT_TIMEOUT, DEFAULT_TIMEOUT, DEFAULT_USER_AGENT.to_string());
+    let client = pool.get(&ProxyChoice::System)?;
+    match get_feed_with_retry(&client, url, &Validators::default()).await? {
+        Fetched::Modified(feed, _) => Ok(feed),
+        // Nothing was cached, so a 304 here is the server misbehaving
+        Fetched::NotModified => Err(FetchError::Status(StatusCode::NOT_MODIFIED)),
+    }
+}
+
+/// Parses an RSS, Atom or JSON Feed document already in memory, newest item first.
+///
+/// ```
+/// let xml = br#"<rss version="2.0"><channel><title>Example</title><link>https://example.com</link>
+///   <description>d</description>
+///   <item><title>Hello</title><link>https://example.com/hello</link></item>
+/// </channel></rss>"#;
+/// let feed = rss_tui::parse_feed(xml).unwrap();
+/// assert_eq!(feed.title, "Example");
+/// assert_eq!(feed.items[0].title, "Hello");
+/// ```
+pub fn parse_feed(content: &[u8]) -> Result<Feed, FetchError> {
+    parse_document(content, false).map_err(|e| FetchError::Parse(e.to_string()))
+}
+
+/// `get_feed`, retried with exponential backoff on transient failures. The last error is returned as is.
+#[tracing::instrument(name = "fetch", skip(client, validators))]
+pub async fn get_feed_with_retry(client: &Client, url: &str, validators: &Validators) -> Result<Fetched, FetchError> {
+    let mut attempt = 0;
+    loop {
+        match get_feed(client, url, validators).await {
+            Err(e) if e.is_transient() && attempt < MAX_RETRIES => {
+                let delay = RETRY_BASE_DELAY * 2u32.pow(attempt);
+                tracing::debug!(attempt, error = %e, "retrying");
+                tokio::time::sleep(delay + jitter(delay / 2)).await;
+                attempt += 1;
+            }
+            result => {
+                match &result {
+                    Ok(Fetched::Modified(feed, _)) => tracing::debug!(items = feed.items.len(), "fetched"),
+                    Ok(Fetched::NotModified) => tracing::debug!("not modified"),
+                    Err(e) => tracing::debug!(error = %e, "failed"),
+                }
+                return result;
+            }
+        }
+    }
+}
+
+/// Up to `max`, so feeds that failed together don't all retry in the same instant.
+fn jitter(max: Duration) -> Duration {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.subsec_nanos());
+    max.mul_f64(f64::from(nanos % 1000) / 1000.0)
+}
+
+/// Parses RSS, Atom or JSON Feed, newest item first. RSS that fails to parse gets a second chance as Atom.
+/// `json_type` is a JSON Content-Type, which settles the format even if the body starts oddly.
+fn parse_document(content: &[u8], json_type: bool) -> Result<Feed, Box<dyn Error + Send + Sync>> {
+    let format = detect_format(content);
+    let mut feed = if json_type || format == Some(FeedFormat::Json) {
+        let feed: JsonFeed = serde_json::from_slice(content)?;
+        if !feed.version.starts_with("https://jsonfeed.org/version/") {
+            return Err(format!("not a JSON Feed (version {:?})", feed.version).into());
+        }
+        from_json(&feed)
+    } else if format == Some(FeedFormat::Atom) {
+        from_atom(&atom_syndication::Feed::read_from(content)?)
+    } else {
+        match Channel::read_from(content) {
+            Ok(channel) => from_rss(&channel),
+            Err(rss_err) => atom_syndication::Feed::read_from(content)
+                .map(|feed| from_atom(&feed))
+                .map_err(|_| rss_err)?,
+        }
+    };
+    sort_newest_first(&mut feed.items);
+    let feed_link = &feed.link;
+    for item in &mut feed.items {
+        // An <img src> is often relative to the article, and get_image needs a full URL
+        let base = if item.link.is_empty() { feed_link } else { &item.link };
+        item.image = item.image.take().map(|src| absolute_url(base, src));
+    }
+    Ok(feed)
+}
+
+/// `src` resolved against `base`; left as it is when either isn't a usable URL.
+fn absolute_url(base: &str, src: String) -> String {
+    reqwest::Url::parse(base).and_then(|base| base.join(&src)).map(String::from).unwrap_or(src)
+}
+
+/// Undated items go last; the sort is stable so ties keep the feed's own order.
+fn sort_newest_first(items: &mut [FeedItem]) {
+    items.sort_by_key(|item| std::cmp::Reverse(item.published));
+}
+
+/// The XML parser decodes documents that declare `encoding="…"` in their prolog itself; this covers
+/// the ones that only name a charset in `Content-Type`, re-encoding them as UTF-8. Anything else is
+/// taken to be UTF-8 already.
+fn to_utf8<'a>(content: &'a [u8], content_type: Option<&str>) -> Cow<'a, [u8]> {
+    let encoding = content_type
+        .and_then(|value| value.split(';').find_map(|param| param.trim().strip_prefix("charset=")))
+        .and_then(|label| Encoding::for_label(label.trim_matches('"').as_bytes()));
+    match encoding {
+        Some(encoding) if encoding != UTF_8 && !declares_encoding(content) => {
+            Cow::Owned(encoding.decode(content).0.into_owned().into_bytes())
+        }
+        _ => Cow::Borrowed(content),
+    }
+}
+
+fn declares_encoding(content: &[u8]) -> bool {
+    let head = String::from_utf8_lossy(&content[..content.len().min(256)]);
+    let head = head.trim_start_matches('\u{feff}').trim_start();
+    head.starts_with("<?xml") && head.split("?>").next().is_some_and(|prolog| prolog.contains("encoding"))
+}
+
+/// Peeks at the first element, skipping the XML declaration, comments and doctype.
+fn detect_format(content: &[u8]) -> Option<FeedFormat> {
+    let text = String::from_utf8_lossy(&content[..content.len().min(4096)]);
+    let mut rest = text.trim_start_matches('\u{feff}');
+    if rest.trim_start().starts_with('{') {
+        return Some(FeedFormat::Json);
+    }
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix("<?") {
+            rest = &after[after.find("?>")? + 2..];
+        } else if let Some(after) = rest.strip_prefix("<!--") {
+            rest = &after[after.find("-->")? + 3..];
+        } else if let Some(after) = rest.strip_prefix("<!") {
+            rest = &after[after.find('>')? + 1..];
+        } else {
+            break;
+        }
+    }
+    let name: String = rest
+        .strip_prefix('<')?
+        .chars()
+        .take_while(|c| !c.is_whitespace() && *c != '>' && *c != '/')
+        .collect();
+    // Ignore any namespace prefix (e.g. <atom:feed>, <rdf:RDF>)
+    match name.rsplit(':').next()? {
+        "feed" => Some(FeedFormat::Atom),
+        "rss" | "RDF" => Some(FeedFormat::Rss),
+        _ => None,
+    }
+}
+
+/// An `<html>` or HTML doctype near the start, for bodies whose Content-Type doesn't say.
+fn looks_like_html(content: &[u8]) -> bool {
+    let head = String::from_utf8_lossy(&content[..content.len().min(1024)]).to_ascii_lowercase();
+    head.contains("<!doctype html") || head.contains("<html")
+}
+
+fn from_rss(channel: &Channel) -> Feed {
     let items = channel
         .items()
         .iter()
         .map(|item| {
             let title = item.title().unwrap_or("No Title").to_string();
-            let date = item.pub_date().unwrap_or("N/A");
-            let short_date = date.split(' ').skip(1).take(2).collect::<Vec<_>>().join(" ");
-            
-            let raw_desc = item.description().unwrap_or("No description available.").to_string();
-            let decoded = html_escape::decode_html_entities(&raw_desc).to_string();
-            
-            let clean_desc = decoded
-                .replace("<p>", "").replace("</p>", "")
-                .replace("<br>", "\n").replace("</br>", "\n")
-                .replace("<em>", "").replace("</em>", "")
-                .replace("<strong>", "").replace("</strong>", "");
-            
-            (title, short_date, clean_desc)
+            FeedItem {
+                title,
+                published: item.pub_date().and_then(parse_date),
+                summary: clean_description(item.description().unwrap_or("No description available.")),
+                full_content: item.content().map(clean_description).filter(|text| !text.is_empty()),
+                link: item.link().unwrap_or_default().to_string(),
+                guid: item.guid().map(|g| g.value().to_string()),
+                categories: item.categories().iter().map(|c| c.name().trim().to_string()).filter(|c| !c.is_empty()).collect(),
+                image: rss_image(item),
+            }
+        })
+        .collect();
+
+    let next_page = channel
+        .atom_ext()
+        .and_then(|ext| ext.links().iter().find(|l| l.rel() == "next"))
+        .map(|l| l.href().to_string());
+
+    Feed {
+        title: channel.title().trim().to_string(),
+        link: channel.link().to_string(),
+        items,
+        next_page,
+    }
+}
+
+fn from_atom(feed: &atom_syndication::Feed) -> Feed {
+    let items = feed
+        .entries()
+        .iter()
+        .map(|entry| {
+            let title = entry.title().as_str().to_string();
+            let date = entry.published().unwrap_or(entry.updated());
+            let content = entry.content().and_then(|c| c.value());
+            let raw_desc = entry.summary().map(|s| s.as_str()).or(content).unwrap_or("No description available.");
+            FeedItem {
+                title,
+                published: Some(date.with_timezone(&Utc)),
+                summary: clean_description(raw_desc),
+                full_content: content.map(clean_description).filter(|text| !text.is_empty()),
+                link: entry
+                    .links()
+                    .iter()
+                    .find(|l| l.rel() == "alternate")
+                    .map(|l| l.href().to_string())
+                    .unwrap_or_default(),
+                guid: Some(entry.id().to_string()),
+                categories: entry
+                    .categories()
+                    .iter()
+                    .map(|c| c.label().unwrap_or(c.term()).trim().to_string())
+                    .filter(|c| !c.is_empty())
+                    .collect(),
+                image: atom_image(entry),
+            }
+        })
+        .collect();
+
+    let link_with_rel = |rel: &str| feed.links().iter().find(|l| l.rel() == rel).map(|l| l.href().to_string());
+
+    Feed {
+        title: feed.title().as_str().trim().to_string(),
+        link: link_with_rel("alternate").unwrap_or_default(),
+        items,
+        next_page: link_with_rel("next"),
+    }
+}
+
+/// A JSON Feed document (jsonfeed.org, 1.0 and 1.1); only the fields we show.
+#[derive(Debug, Deserialize)]
+struct JsonFeed {
+    /// Required, and what tells a JSON Feed apart from any other JSON
+    version: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    home_page_url: Option<String>,
+    #[serde(default)]
+    next_url: Option<String>,
+    #[serde(default)]
+    items: Vec<JsonFeedItem>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct JsonFeedItem {
+    /// A string in 1.1, but 1.0 feeds sometimes send a number
+    id: serde_json::Value,
+    url: Option<String>,
+    external_url: Option<String>,
+    title: Option<String>,
+    content_html: Option<String>,
+    content_text: Option<String>,
+    summary: Option<String>,
+    image: Option<String>,
+    banner_image: Option<String>,
+    date_published: Option<String>,
+    date_modified: Option<String>,
+    tags: Vec<String>,
+    attachments: Vec<JsonFeedAttachment>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct JsonFeedAttachment {
+    url: String,
+    mime_type: String,
+}
+
+fn from_json(feed: &JsonFeed) -> Feed {
+    let items = feed
+        .items
+        .iter()
+        .map(|item| {
+            let content = item.content_html.as_deref().or(item.content_text.as_deref()).map(clean_description);
+            let summary = item.summary.as_deref().map(clean_description).or_else(|| content.clone());
+            // Title-less posts (microblogs) are common in JSON Feed; their first line stands in
+            let title = item
+                .title
+                .clone()
+                .filter(|t| !t.trim().is_empty())
+                .or_else(|| summary.as_deref().and_then(|s| s.lines().next()).map(str::to_string))
+                .unwrap_or_else(|| "No Title".to_string());
+            let guid = match &item.id {
+                serde_json::Value::String(id) => Some(id.clone()),
+                serde_json::Value::Number(id) => Some(id.to_string()),
+                _ => None,
+            };
+            let image = item
+                .image
+                .clone()
+                .or_else(|| item.banner_image.clone())
+                .or_else(|| item.attachments.iter().find(|a| a.mime_type.starts_with("image/")).map(|a| a.url.clone()))
+                .or_else(|| item.content_html.as_deref().and_then(img_src));
+            FeedItem {
+                title,
+                published: item.date_published.as_deref().or(item.date_modified.as_deref()).and_then(parse_date),
+                summary: summary.unwrap_or_else(|| "No description available.".to_string()),
+                full_content: content.filter(|text| !text.is_empty()),
+                link: item.url.clone().or_else(|| item.external_url.clone()).unwrap_or_default(),
+                guid,
+                categories: item.tags.iter().map(|t| t.trim().to_string()).filter(|t| !t.is_empty()).collect(),
+                image,
+            }
         })
         .collect();
 
-    Ok(items)
-}
\ No newline at end of file
+    Feed {
+        title: feed.title.trim().to_string(),
+        link: feed.home_page_url.clone().unwrap_or_default(),
+        items,
+        next_page: feed.next_url.clone(),
+    }
+}
+
+fn rss_image(item: &rss::Item) -> Option<String> {
+    item.enclosure()
+        .filter(|e| e.mime_type().starts_with("image/"))
+        .map(|e| e.url().to_string())
+        .or_else(|| media_image(item.extensions().get("media")))
+        .or_else(|| item.description().and_then(img_src))
+        .or_else(|| item.content().and_then(img_src))
+}
+
+fn atom_image(entry: &atom_syndication::Entry) -> Option<String> {
+    entry
+        .links()
+        .iter()
+        .find(|l| l.rel() == "enclosure" && l.mime_type().is_some_and(|t| t.starts_with("image/")))
+        .map(|l| l.href().to_string())
+        .or_else(|| media_image(entry.extensions().get("media")))
+        .or_else(|| entry.summary().and_then(|s| img_src(s.as_str())))
+        .or_else(|| entry.content().and_then(|c| c.value()).and_then(img_src))
+}
+
+/// What `media_image` needs from an extension element; rss and atom_syndication each have their own type.
+trait ExtensionNode: Sized {
+    fn attr(&self, name: &str) -> Option<&str>;
+    fn child_map(&self) -> &BTreeMap<String, Vec<Self>>;
+}
+
+impl ExtensionNode for rss::extension::Extension {
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs().get(name).map(String::as_str)
+    }
+
+    fn child_map(&self) -> &BTreeMap<String, Vec<Self>> {
+        self.children()
+    }
+}
+
+impl ExtensionNode for atom_syndication::extension::Extension {
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs().get(name).map(String::as_str)
+    }
+
+    fn child_map(&self) -> &BTreeMap<String, Vec<Self>> {
+        self.children()
+    }
+}
+
+/// The first image among Media RSS elements: an image `media:content`, else a `media:thumbnail`,
+/// else the same inside a `media:group`.
+fn media_image<E: ExtensionNode>(media: Option<&BTreeMap<String, Vec<E>>>) -> Option<String> {
+    let media = media?;
+    let elements = |name: &str| media.get(name).map_or(&[][..], Vec::as_slice);
+    elements("content")
+        .iter()
+        .find(|e| e.attr("medium") == Some("image") || e.attr("type").is_some_and(|t| t.starts_with("image/")))
+        .or_else(|| elements("thumbnail").first())
+        .and_then(|e| e.attr("url"))
+        .map(str::to_string)
+        .or_else(|| elements("group").iter().find_map(|group| media_image(Some(group.child_map()))))
+}
+
+/// `src` of the first `<img>` in an HTML fragment, which is often entity-escaped inside the XML.
+fn img_src(html: &str) -> Option<String> {
+    let html = html_escape::decode_html_entities(html);
+    // ASCII lowercasing keeps byte offsets, so positions found in `lower` index `html` too
+    let lower = html.to_ascii_lowercase();
+    let start = lower.find("<img")?;
+    let end = start + lower[start..].find('>')?;
+    let attr = start + lower[start..end].find("src=")? + "src=".len();
+    let rest = &html[attr..end];
+    let src = match rest.chars().next()? {
+        quote @ ('"' | '\'') => rest[1..].split(quote).next()?,
+        _ => rest.split_whitespace().next()?,
+    };
+    let src = src.trim();
+    (!src.is_empty()).then(|| src.to_string())
+}
+
+/// RSS promises RFC 822 dates, but plenty of feeds send RFC 3339 instead.
+fn parse_date(raw: &str) -> Option<DateTime<Utc>> {
+    let raw = raw.trim();
+    DateTime::parse_from_rfc2822(raw)
+        .or_else(|_| DateTime::parse_from_rfc3339(raw))
+        .ok()
+        .map(|d| d.with_timezone(&Utc))
+}
+
+/// Strips every tag, keeping the text, and decodes entities exactly once. Line breaks and block
+/// elements become newlines; scripts and styles vanish.
+fn clean_description(raw_desc: &str) -> String {
+    // Markup escaped once more than usual (`&lt;p&gt;`, common inside CDATA) is unescaped to find
+    // the tags; everything else keeps its entities until the tags are gone, so `&amp;lt;` stays `&lt;`
+    if !raw_desc.contains('<') && raw_desc.contains("&lt;") {
+        return strip_tags(&html_escape::decode_html_entities(raw_desc));
+    }
+    html_escape::decode_html_entities(&strip_tags(raw_desc)).into_owned()
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let Some(len) = tag_len(&rest[start..]) else {
+            // A lone '<' is just text
+            text.push('<');
+            rest = &rest[start + 1..];
+            continue;
+        };
+        let tag = &rest[start + 1..start + len - 1];
+        rest = &rest[start + len..];
+
+        let name = tag
+            .trim_start_matches('/')
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        if !tag.starts_with('/') && (name == "script" || name == "style") {
+            let close = format!("</{}", name);
+            rest = match rest.to_ascii_lowercase().find(&close) {
+                Some(end) => &rest[end..],
+                None => "",
+            };
+            continue;
+        }
+        let breaks_line = matches!(
+            name.as_str(),
+            "br" | "p" | "div" | "li" | "ul" | "ol" | "tr" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6"
+        );
+        if breaks_line && !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+    }
+    text.push_str(rest);
+    text.trim().to_string()
+}
+
+/// Length of the tag at the start of `s` through its '>', ignoring any '>' inside quoted attributes.
+fn tag_len(s: &str) -> Option<usize> {
+    // Only "<a", "</" or "<!" open a tag; "a < b" is text
+    let first = s[1..].chars().next()?;
+    if !(first.is_ascii_alphabetic() || first == '/' || first == '!') {
+        return None;
+    }
+    let mut quote = None;
+    for (i, c) in s.char_indices().skip(1) {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return Some(i + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+
+    /// A raw HTTP/1.1 response that closes the connection after itself.
+    fn reply(status: &str, headers: &[(&str, &str)], body: &str) -> String {
+        let mut out = format!("HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n", status, body.len());
+        for (name, value) in headers {
+            out.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        out + "\r\n" + body
+    }
+
+    /// Answers one connection per response, in order, on a local port. Returns the base URL and
+    /// the head of each request as it arrives.
+    async fn serve(responses: Vec<String>) -> (String, mpsc::UnboundedReceiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            for response in responses {
+                let Ok((mut socket, _)) = listener.accept().await else { return };
+                let mut head = Vec::new();
+                let mut buf = [0; 1024];
+                while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match socket.read(&mut buf).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => head.extend_from_slice(&buf[..n]),
+                    }
+                }
+                let _ = tx.send(String::from_utf8_lossy(&head).into_owned());
+                let _ = socket.write_all(response.as_bytes()).await;
+                let _ = socket.shutdown().await;
+            }
+        });
+        (base, rx)
+    }
+
+    fn test_client() -> Client {
+        ClientPool::new(DEFAULT_CONNECT_TIMEOUT, DEFAULT_TIMEOUT, DEFAULT_USER_AGENT.to_string())
+            .get(&ProxyChoice::Direct)
+            .unwrap()
+    }
+
+    /// An RSS document with one item titled "Café déjà vu", encoded as Windows-1252.
+    fn cp1252_rss(prolog: &str) -> Vec<u8> {
+        let mut doc = format!("{}<rss version=\"2.0\"><channel><title>T</title><item><title>", prolog).into_bytes();
+        doc.extend_from_slice(b"Caf\xe9 d\xe9j\xe0 vu");
+        doc.extend_from_slice(b"</title></item></channel></rss>");
+        doc
+    }
+
+    #[test]
+    fn decodes_charset_from_the_prolog() {
+        let doc = cp1252_rss("<?xml version=\"1.0\" encoding=\"windows-1252\"?>");
+        // The prolog wins, so a wrong Content-Type is ignored rather than decoding twice
+        let content = to_utf8(&doc, Some("text/xml; charset=iso-8859-1"));
+        assert!(matches!(content, Cow::Borrowed(_)));
+        assert_eq!(parse_feed(&content).unwrap().items[0].title, "Café déjà vu");
+    }
+
+    #[test]
+    fn decodes_charset_from_the_content_type() {
+        let doc = cp1252_rss("<?xml version=\"1.0\"?>");
+        let content = to_utf8(&doc, Some("application/rss+xml; charset=\"windows-1252\""));
+        assert_eq!(parse_feed(&content).unwrap().items[0].title, "Café déjà vu");
+        // Without either hint the bytes are taken to be UTF-8 as they are
+        assert!(matches!(to_utf8(&doc, Some("text/xml")), Cow::Borrowed(_)));
+    }
+
+    const LOGIN_PAGE: &str = "<!DOCTYPE html>\n<html><head><title>Sign in</title></head><body><form></form></body></html>";
+    const TINY_RSS: &str = "<rss version=\"2.0\"><channel><title>T</title><item><title>One</title></item></channel></rss>";
+
+    #[test]
+    fn detects_formats_past_boms_and_prologs() {
+        let bom_rss = format!("\u{feff}<?xml version=\"1.0\"?>\n<!-- generated -->\n{}", TINY_RSS);
+        assert_eq!(detect_format(bom_rss.as_bytes()), Some(FeedFormat::Rss));
+        assert_eq!(detect_format(b"<?xml version=\"1.0\"?><feed xmlns=\"http://www.w3.org/2005/Atom\"/>"), Some(FeedFormat::Atom));
+        assert_eq!(detect_format(b"<rdf:RDF></rdf:RDF>"), Some(FeedFormat::Rss));
+        assert_eq!(detect_format(b"  {\"version\": \"x\"}"), Some(FeedFormat::Json));
+        assert_eq!(detect_format(LOGIN_PAGE.as_bytes()), None);
+    }
+
+    #[test]
+    fn spots_html_pages() {
+        assert!(looks_like_html(LOGIN_PAGE.as_bytes()));
+        assert!(looks_like_html(b"<HTML lang=\"en\"><body>Moved</body></HTML>"));
+        assert!(!looks_like_html(TINY_RSS.as_bytes()));
+    }
+
+    #[tokio::test]
+    async fn html_login_page_is_not_a_feed() {
+        let (base, _requests) = serve(vec![reply("200 OK", &[("Content-Type", "text/html; charset=utf-8")], LOGIN_PAGE)]).await;
+        let url = format!("{}/feed.xml", base);
+        match get_feed(&test_client(), &url, &Validators::default()).await {
+            Err(FetchError::NotAFeed { url: at, redirected }) => {
+                assert_eq!(at, url);
+                assert!(!redirected);
+            }
+            other => panic!("expected NotAFeed, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[tokio::test]
+    async fn rss_labelled_as_html_still_parses() {
+        let (base, _requests) = serve(vec![reply("200 OK", &[("Content-Type", "text/html")], TINY_RSS)]).await;
+        match get_feed(&test_client(), &format!("{}/feed.xml", base), &Validators::default()).await {
+            Ok(Fetched::Modified(feed, _)) => assert_eq!(feed.items[0].title, "One"),
+            other => panic!("expected a feed, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[tokio::test]
+    async fn server_errors_are_retried_until_the_feed_arrives() {
+        let responses = vec![reply("503 Service Unavailable", &[], ""), reply("502 Bad Gateway", &[], ""), reply("200 OK", &[], TINY_RSS)];
+        let (base, mut requests) = serve(responses).await;
+        match get_feed_with_retry(&test_client(), &format!("{}/feed.xml", base), &Validators::default()).await {
+            Ok(Fetched::Modified(feed, _)) => assert_eq!(feed.items[0].title, "One"),
+            other => panic!("expected a feed, got {:?}", other.map(|_| ())),
+        }
+        let mut attempts = 0;
+        while requests.try_recv().is_ok() {
+            attempts += 1;
+        }
+        assert_eq!(attempts, 1 + MAX_RETRIES);
+    }
+
+    #[tokio::test]
+    async fn proxied_clients_send_requests_to_the_proxy() {
+        let (proxy, mut requests) = serve(vec![reply("200 OK", &[], TINY_RSS)]).await;
+        let pool = ClientPool::new(DEFAULT_CONNECT_TIMEOUT, DEFAULT_TIMEOUT, DEFAULT_USER_AGENT.to_string());
+        let client = pool.get(&ProxyChoice::Via(proxy)).unwrap();
+        // The feed's host doesn't exist; only the proxy is ever connected to
+        let fetched = get_feed(&client, "http://feeds.invalid/rss", &Validators::default()).await;
+        assert!(matches!(fetched, Ok(Fetched::Modified(..))), "{:?}", fetched.map(|_| ()));
+        let head = requests.recv().await.unwrap();
+        assert!(head.starts_with("GET http://feeds.invalid/rss HTTP/1.1"), "{}", head);
+    }
+
+    #[test]
+    fn invalid_proxy_urls_are_rejected() {
+        let pool = ClientPool::new(DEFAULT_CONNECT_TIMEOUT, DEFAULT_TIMEOUT, DEFAULT_USER_AGENT.to_string());
+        assert!(pool.get(&ProxyChoice::Via("http://[not a proxy".to_string())).is_err());
+    }
+
+    #[test]
+    fn parses_rfc822_and_rfc3339_dates() {
+        use chrono::TimeZone;
+        let expected = Some(Utc.with_ymd_and_hms(2024, 1, 2, 15, 4, 5).unwrap());
+        assert_eq!(parse_date("Tue, 02 Jan 2024 15:04:05 GMT"), expected);
+        assert_eq!(parse_date("Tue, 02 Jan 2024 16:04:05 +0100"), expected);
+        assert_eq!(parse_date(" 2024-01-02T15:04:05Z\n"), expected);
+        assert_eq!(parse_date("2024-01-02T10:04:05-05:00"), expected);
+    }
+
+    #[test]
+    fn malformed_dates_show_as_na() {
+        for raw in ["", "yesterday", "02/01/2024", "Tue, 32 Jan 2024 15:04:05 GMT"] {
+            assert_eq!(parse_date(raw), None, "{:?}", raw);
+        }
+        assert_eq!(FeedItem { published: parse_date("soon"), ..FeedItem::default() }.short_date(None), "N/A");
+        // Shown in the configured zone: late evening UTC is already the next day in Tokyo
+        let item = FeedItem { published: parse_date("2024-01-02T23:30:00Z"), ..FeedItem::default() };
+        assert_eq!(item.short_date(Some(chrono_tz::Asia::Tokyo)), "Jan 03");
+    }
+
+    #[test]
+    fn descriptions_keep_only_readable_text() {
+        let html = "<p>Read <a href=\"https://example.com/?a=1&amp;b=2\" title=\"x > y\">the story</a>.</p>\
+                    <img src=\"https://example.com/pic.jpg\" alt=\"A photo\"/><ul><li>One</li><li>Two &amp; three</li></ul>";
+        assert_eq!(clean_description(html), "Read the story.\nOne\nTwo & three");
+    }
+
+    #[test]
+    fn entities_are_decoded_once() {
+        assert_eq!(clean_description("<p>Use &amp;lt;br&amp;gt; for breaks</p>"), "Use &lt;br&gt; for breaks");
+        // Markup that arrives escaped is still stripped
+        assert_eq!(clean_description("&lt;p&gt;Tom &amp; Jerry&lt;/p&gt;"), "Tom & Jerry");
+    }
+
+    const ATOM_FEED: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Example Atom</title>
+  <link href="https://example.org/"/>
+  <id>urn:uuid:60a76c80-d399-11d9-b93c-0003939e0af6</id>
+  <updated>2026-03-02T09:00:00Z</updated>
+  <entry>
+    <title>Atom-Powered Robots Run Amok</title>
+    <link rel="alternate" href="https://example.org/2026/03/02/atom"/>
+    <id>urn:uuid:1225c695-cfb8-4ebb-aaaa-80da344efa6a</id>
+    <updated>2026-03-02T09:00:00Z</updated>
+    <published>2026-03-01T18:30:00Z</published>
+    <summary>Some text.</summary>
+    <category term="robots" label="Robots"/>
+  </entry>
+</feed>"#;
+
+    #[test]
+    fn parses_atom_entries() {
+        let feed = parse_feed(ATOM_FEED.as_bytes()).unwrap();
+        let [entry] = &feed.items[..] else { panic!("expected one entry, got {:?}", feed.items) };
+        assert_eq!(entry.title, "Atom-Powered Robots Run Amok");
+        assert_eq!(entry.link, "https://example.org/2026/03/02/atom");
+        assert_eq!(entry.guid.as_deref(), Some("urn:uuid:1225c695-cfb8-4ebb-aaaa-80da344efa6a"));
+        // <published> wins over <updated>
+        assert_eq!(entry.published, Some("2026-03-01T18:30:00Z".parse().unwrap()));
+        assert_eq!(entry.summary, "Some text.");
+        assert_eq!(entry.categories, ["Robots"]);
+    }
+
+    #[test]
+    fn items_sort_newest_first_with_undated_last() {
+        let doc = "<rss version=\"2.0\"><channel><title>T</title>\
+            <item><title>middle</title><pubDate>Mon, 02 Mar 2026 09:00:00 GMT</pubDate></item>\
+            <item><title>undated</title></item>\
+            <item><title>oldest</title><pubDate>Sun, 01 Mar 2026 09:00:00 GMT</pubDate></item>\
+            <item><title>garbled</title><pubDate>sometime</pubDate></item>\
+            <item><title>newest</title><pubDate>2026-03-03T09:00:00Z</pubDate></item>\
+            </channel></rss>";
+        let titles: Vec<String> = parse_feed(doc.as_bytes()).unwrap().items.into_iter().map(|item| item.title).collect();
+        // Undated items keep the feed's own order between them
+        assert_eq!(titles, ["newest", "middle", "oldest", "undated", "garbled"]);
+    }
+
+    #[test]
+    fn content_encoded_is_the_body_and_description_the_summary() {
+        let doc = "<rss version=\"2.0\" xmlns:content=\"http://purl.org/rss/1.0/modules/content/\"><channel><title>T</title><item>\
+            <title>Both</title><description>Short teaser</description>\
+            <content:encoded><![CDATA[<p>The whole article.</p><p>Second paragraph.</p>]]></content:encoded>\
+            </item><item><title>Teaser only</title><description>Just this</description></item></channel></rss>";
+        let feed = parse_feed(doc.as_bytes()).unwrap();
+        let [both, teaser] = &feed.items[..] else { panic!("expected two items, got {:?}", feed.items) };
+        assert_eq!(both.summary, "Short teaser");
+        assert_eq!(both.full_content.as_deref(), Some("The whole article.\nSecond paragraph."));
+        assert_eq!(both.body(), "The whole article.\nSecond paragraph.");
+        assert_eq!(teaser.full_content, None);
+        assert_eq!(teaser.body(), "Just this");
+    }
+
+    #[tokio::test]
+    async fn a_server_that_never_answers_times_out() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/feed.xml", listener.local_addr().unwrap());
+        // Accepts the connection, then says nothing back
+        tokio::spawn(async move {
+            let (_socket, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(Duration::from_secs(30)).await;
+        });
+        let client = ClientPool::new(DEFAULT_CONNECT_TIMEOUT, Duration::from_millis(200), DEFAULT_USER_AGENT.to_string())
+            .get(&ProxyChoice::Direct)
+            .unwrap();
+        match get_feed(&client, &url, &Validators::default()).await {
+            Err(FetchError::Network(msg)) => assert_eq!(msg, "timed out"),
+            other => panic!("expected a network error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[tokio::test]
+    async fn validators_turn_the_second_fetch_into_a_304() {
+        let etag = ("ETag", "\"v1\"");
+        let modified = ("Last-Modified", "Mon, 02 Mar 2026 09:00:00 GMT");
+        let (base, mut requests) = serve(vec![reply("200 OK", &[etag, modified], TINY_RSS), reply("304 Not Modified", &[], "")]).await;
+        let url = format!("{}/feed.xml", base);
+
+        let Ok(Fetched::Modified(_, validators)) = get_feed(&test_client(), &url, &Validators::default()).await else {
+            panic!("expected the first fetch to bring the feed");
+        };
+        assert_eq!(validators.etag.as_deref(), Some("\"v1\""));
+        let first = requests.recv().await.unwrap().to_ascii_lowercase();
+        assert!(!first.contains("if-none-match"), "{}", first);
+
+        let second = get_feed(&test_client(), &url, &validators).await;
+        assert!(matches!(second, Ok(Fetched::NotModified)), "{:?}", second.map(|_| ()));
+        let head = requests.recv().await.unwrap().to_ascii_lowercase();
+        assert!(head.contains("if-none-match: \"v1\""), "{}", head);
+        assert!(head.contains("if-modified-since: mon, 02 mar 2026 09:00:00 gmt"), "{}", head);
+    }
+
+    #[tokio::test]
+    async fn requests_carry_the_user_agent() {
+        let (base, mut requests) = serve(vec![reply("200 OK", &[], TINY_RSS), reply("200 OK", &[], TINY_RSS)]).await;
+        let url = format!("{}/feed.xml", base);
+        get_feed(&test_client(), &url, &Validators::default()).await.unwrap();
+        let head = requests.recv().await.unwrap().to_ascii_lowercase();
+        assert!(head.contains(&format!("user-agent: {}\r\n", DEFAULT_USER_AGENT)), "{}", head);
+
+        let custom = ClientPool::new(DEFAULT_CONNECT_TIMEOUT, DEFAULT_TIMEOUT, "my-reader/2.0".to_string());
+        get_feed(&custom.get(&ProxyChoice::Direct).unwrap(), &url, &Validators::default()).await.unwrap();
+        let head = requests.recv().await.unwrap().to_ascii_lowercase();
+        assert!(head.contains("user-agent: my-reader/2.0\r\n"), "{}", head);
+    }
+
+    #[tokio::test]
+    async fn endless_redirects_stop_at_the_limit() {
+        // Every hop points one further along: /0 -> /1 -> /2 ...
+        let hops: Vec<String> = (1..=MAX_REDIRECTS + 1).map(|n| reply("302 Found", &[("Location", &format!("/{}", n))], "")).collect();
+        let (base, _requests) = serve(hops).await;
+        match get_feed(&test_client(), &format!("{}/0", base), &Validators::default()).await {
+            Err(FetchError::TooManyRedirects(last)) => assert!(last.as_ref().is_some_and(|url| url.starts_with(&base)), "{:?}", last),
+            other => panic!("expected TooManyRedirects, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    fn first_rss_item(items: &str) -> rss::Item {
+        let doc = format!(
+            "<rss version=\"2.0\" xmlns:media=\"http://search.yahoo.com/mrss/\"><channel><title>T</title>{}</channel></rss>",
+            items
+        );
+        Channel::read_from(doc.as_bytes()).unwrap().items()[0].clone()
+    }
+
+    #[test]
+    fn finds_rss_images() {
+        let enclosure = first_rss_item(
+            "<item><enclosure url=\"https://cdn.example/a.jpg\" type=\"image/jpeg\" length=\"1\"/>\
+             <description>&lt;img src=\"https://cdn.example/b.jpg\"&gt;</description></item>",
+        );
+        assert_eq!(rss_image(&enclosure).as_deref(), Some("https://cdn.example/a.jpg"));
+        // An audio enclosure is skipped for the picture in the description
+        let podcast = first_rss_item(
+            "<item><enclosure url=\"https://cdn.example/a.mp3\" type=\"audio/mpeg\" length=\"1\"/>\
+             <description>&lt;p&gt;&lt;img alt=\"x\" src=\"https://cdn.example/b.jpg\"&gt;&lt;/p&gt;</description></item>",
+        );
+        assert_eq!(rss_image(&podcast).as_deref(), Some("https://cdn.example/b.jpg"));
+        assert_eq!(rss_image(&first_rss_item("<item><description>No pictures</description></item>")), None);
+    }
+
+    #[test]
+    fn finds_media_rss_images() {
+        let media = |item: &rss::Item| media_image(item.extensions().get("media"));
+        let content = first_rss_item(
+            "<item><media:content url=\"https://cdn.example/v.mp4\" medium=\"video\"/>\
+             <media:content url=\"https://cdn.example/c.jpg\" medium=\"image\"/></item>",
+        );
+        assert_eq!(media(&content).as_deref(), Some("https://cdn.example/c.jpg"));
+        let thumbnail = first_rss_item("<item><media:thumbnail url=\"https://cdn.example/t.jpg\"/></item>");
+        assert_eq!(media(&thumbnail).as_deref(), Some("https://cdn.example/t.jpg"));
+        let group = first_rss_item(
+            "<item><media:group><media:content url=\"https://cdn.example/g.png\" type=\"image/png\"/></media:group></item>",
+        );
+        assert_eq!(media(&group).as_deref(), Some("https://cdn.example/g.png"));
+        assert_eq!(media(&first_rss_item("<item><title>Plain</title></item>")), None);
+    }
+
+    #[test]
+    fn finds_atom_images() {
+        let doc = "<feed xmlns=\"http://www.w3.org/2005/Atom\"><title>T</title><id>f</id><updated>2026-03-01T00:00:00Z</updated>\
+            <entry><title>A</title><id>a</id><updated>2026-03-01T00:00:00Z</updated>\
+              <link rel=\"enclosure\" type=\"image/webp\" href=\"https://cdn.example/e.webp\"/></entry>\
+            <entry><title>B</title><id>b</id><updated>2026-03-01T00:00:00Z</updated>\
+              <content type=\"html\">&lt;img src='https://cdn.example/f.gif'&gt;</content></entry>\
+            </feed>";
+        let feed = atom_syndication::Feed::read_from(doc.as_bytes()).unwrap();
+        assert_eq!(atom_image(&feed.entries()[0]).as_deref(), Some("https://cdn.example/e.webp"));
+        assert_eq!(atom_image(&feed.entries()[1]).as_deref(), Some("https://cdn.example/f.gif"));
+    }
+
+    #[test]
+    fn extracts_img_src() {
+        assert_eq!(img_src("<p>Hi <IMG SRC=\"/a.png\" alt=x></p>").as_deref(), Some("/a.png"));
+        assert_eq!(img_src("&lt;img class=\"wide\" src='b.jpg'&gt;").as_deref(), Some("b.jpg"));
+        assert_eq!(img_src("<img src=c.gif width=10>").as_deref(), Some("c.gif"));
+        assert_eq!(img_src("<img src=\"\">"), None);
+        assert_eq!(img_src("<p>no images</p>"), None);
+    }
+
+    #[test]
+    fn relative_images_resolve_against_the_item() {
+        let doc = "<rss version=\"2.0\"><channel><title>T</title><link>https://example.org/</link>\
+            <item><link>https://example.org/posts/1</link><description>&lt;img src=\"img/a.png\"&gt;</description></item>\
+            <item><description>&lt;img src=\"/static/b.png\"&gt;</description></item>\
+            </channel></rss>";
+        let feed = parse_feed(doc.as_bytes()).unwrap();
+        assert_eq!(feed.items[0].image.as_deref(), Some("https://example.org/posts/img/a.png"));
+        // No item link: the channel's stands in
+        assert_eq!(feed.items[1].image.as_deref(), Some("https://example.org/static/b.png"));
+    }
+
+    #[test]
+    fn retry_after_seconds_and_dates() {
+        let now: DateTime<Utc> = "2026-03-01T12:00:00Z".parse().unwrap();
+        assert_eq!(parse_retry_after("120", now), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after(" 0 ", now), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("Sun, 01 Mar 2026 12:01:30 GMT", now), Some(Duration::from_secs(90)));
+        // Already past: no need to wait
+        assert_eq!(parse_retry_after("Sun, 01 Mar 2026 11:00:00 GMT", now), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("soon", now), None);
+        assert_eq!(parse_retry_after("-5", now), None);
+        assert_eq!(parse_retry_after("", now), None);
+    }
+
+    #[tokio::test]
+    async fn too_many_requests_reports_the_wait() {
+        let (base, _requests) = serve(vec![reply("429 Too Many Requests", &[("Retry-After", "30")], "")]).await;
+        match get_feed(&test_client(), &format!("{}/feed.xml", base), &Validators::default()).await {
+            Err(FetchError::RateLimited(wait)) => assert_eq!(wait, Some(Duration::from_secs(30))),
+            other => panic!("expected RateLimited, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    const JSON_FEED: &str = r#"{
+        "version": "https://jsonfeed.org/version/1.1",
+        "title": "Sample",
+        "home_page_url": "https://example.org/",
+        "items": [
+            {
+                "id": "post-1",
+                "url": "https://example.org/1",
+                "title": "First post",
+                "content_html": "<p>Hello <b>world</b></p>",
+                "date_published": "2026-03-01T10:00:00Z",
+                "tags": ["rust", " tui "]
+            },
+            {
+                "id": 42,
+                "content_text": "Just a note\nwith a second line",
+                "date_published": "2026-03-02T10:00:00Z"
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn parses_json_feed() {
+        let feed = parse_feed(JSON_FEED.as_bytes()).unwrap();
+        assert_eq!(feed.title, "Sample");
+        assert_eq!(feed.link, "https://example.org/");
+        // Newest first, so the note comes before the post
+        let [note, post] = &feed.items[..] else { panic!("expected two items, got {:?}", feed.items) };
+
+        assert_eq!(post.title, "First post");
+        assert_eq!(post.link, "https://example.org/1");
+        assert_eq!(post.guid.as_deref(), Some("post-1"));
+        assert_eq!(post.full_content.as_deref(), Some("Hello world"));
+        assert_eq!(post.published, Some("2026-03-01T10:00:00Z".parse().unwrap()));
+        assert_eq!(post.categories, ["rust", "tui"]);
+
+        assert_eq!(note.title, "Just a note");
+        assert_eq!(note.guid.as_deref(), Some("42"));
+        assert_eq!(note.key(), "42");
+    }
+
+    #[test]
+    fn rejects_json_without_a_feed_version() {
+        let err = parse_feed(br#"{"version": "1.0", "items": []}"#).unwrap_err();
+        assert!(err.to_string().contains("not a JSON Feed"), "{}", err);
+        assert!(parse_feed(br#"{"name": "package.json"}"#).is_err());
+    }
+}
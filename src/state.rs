@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+const DEFAULT_STATE_PATH: &str = "state.json";
+// Bumped when a field changes meaning; files from a newer build are ignored rather than misread
+const STATE_VERSION: i64 = 1;
+
+/// UI choices remembered between runs (unlike `Config`, written by the app itself).
+/// Feeds are keyed by URL, so reordering or removing feeds in the config can't misplace anything.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiState {
+    pub version: i64,
+    pub show_telegram: bool,
+    /// Each feed's rotation/scroll position as an item index
+    pub offsets: HashMap<String, usize>,
+    /// URL of the feed that had focus
+    pub focused_feed: Option<String>,
+    pub view: SavedView,
+    /// Highlighted row of the merged timeline
+    pub timeline_selected: usize,
+    /// Theme picked with [C]; unset until the user switches, so the config's choice applies
+    pub theme: Option<String>,
+}
+
+/// `ViewMode` with the zoomed feed named by URL.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SavedView {
+    #[default]
+    Grid,
+    Focused(String),
+    Timeline,
+    Bookmarks,
+}
+
+impl Default for UiState {
+    fn default() -> Self {
+        Self {
+            version: STATE_VERSION,
+            show_telegram: true,
+            offsets: HashMap::new(),
+            focused_feed: None,
+            view: SavedView::default(),
+            timeline_selected: 0,
+            theme: None,
+        }
+    }
+}
+
+impl UiState {
+    /// Missing, unreadable or newer-than-this-build state just means defaults.
+    pub fn load() -> Self {
+        fs::read_to_string(state_path()).map_or_else(|_| Self::default(), |raw| Self::parse(&raw))
+    }
+
+    fn parse(raw: &str) -> Self {
+        let Ok(raw) = serde_json::from_str::<serde_json::Value>(raw) else {
+            return Self::default();
+        };
+        // Files written before the version key existed only held show_telegram, which still reads fine
+        let version = raw.get("version").and_then(|v| v.as_i64()).unwrap_or(0);
+        if version > STATE_VERSION {
+            return Self::default();
+        }
+        let mut state: UiState = serde_json::from_value(raw).unwrap_or_default();
+        state.version = STATE_VERSION;
+        state
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        fs::write(state_path(), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+fn state_path() -> PathBuf {
+    env::var("RSS_TUI_STATE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(DEFAULT_STATE_PATH))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_newer_file_falls_back_to_defaults() {
+        let state = UiState::parse(&format!(r#"{{"version": {}, "show_telegram": false, "timeline_selected": 4}}"#, STATE_VERSION + 1));
+        assert!(state.show_telegram);
+        assert_eq!(state.timeline_selected, 0);
+    }
+
+    #[test]
+    fn a_file_from_before_versions_still_loads() {
+        let state = UiState::parse(r#"{"show_telegram": false}"#);
+        assert!(!state.show_telegram);
+        assert_eq!(state.version, STATE_VERSION);
+        assert_eq!(state.view, SavedView::Grid);
+        assert!(state.offsets.is_empty());
+    }
+
+    #[test]
+    fn round_trips_and_shrugs_off_garbage() {
+        let mut state = UiState { show_telegram: false, view: SavedView::Focused("https://a.example/rss".into()), ..UiState::default() };
+        state.offsets.insert("https://a.example/rss".into(), 7);
+        let back = UiState::parse(&serde_json::to_string(&state).unwrap());
+        assert_eq!(back.view, state.view);
+        assert_eq!(back.offsets, state.offsets);
+        assert!(!back.show_telegram);
+
+        assert!(UiState::parse("{not json").show_telegram);
+        assert!(UiState::parse(r#"{"version": 1, "offsets": "oops"}"#).offsets.is_empty());
+    }
+}
@@ -0,0 +1,254 @@
+use chrono::{DateTime, Duration, Utc};
+use sqlite::{Connection, State};
+use std::collections::HashSet;
+use std::env;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_STORE_PATH: &str = "read.db";
+/// `MIGRATIONS[n]` takes a schema at `PRAGMA user_version` n to n+1.
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE read_items (key TEXT PRIMARY KEY, read_at INTEGER NOT NULL);",
+    "CREATE TABLE bookmarks (
+        feed_url TEXT NOT NULL,
+        key TEXT NOT NULL,
+        title TEXT NOT NULL,
+        link TEXT NOT NULL,
+        published INTEGER,
+        starred_at INTEGER NOT NULL,
+        PRIMARY KEY (feed_url, key)
+    );",
+];
+
+/// A starred item, copied out of its feed so it outlives the feed dropping it.
+#[derive(Debug, Clone)]
+pub struct Bookmark {
+    pub feed_url: String,
+    /// `FeedItem::key` of the item
+    pub key: String,
+    pub title: String,
+    pub link: String,
+    pub published: Option<DateTime<Utc>>,
+    pub starred_at: DateTime<Utc>,
+}
+
+/// Items the user has selected or opened, plus starred ones, keyed by `FeedItem::key`, kept across runs.
+pub struct ReadStore {
+    conn: Connection,
+}
+
+impl ReadStore {
+    /// Opens `read.db` (or `$RSS_TUI_DB`), creating or upgrading the schema as needed.
+    pub fn open_default() -> sqlite::Result<Self> {
+        Self::open(&store_path())
+    }
+
+    pub fn open(path: &Path) -> sqlite::Result<Self> {
+        let conn = Connection::open(path)?;
+        let mut statement = conn.prepare("PRAGMA user_version")?;
+        let version = match statement.next()? {
+            State::Row => statement.read::<i64, _>(0)? as usize,
+            State::Done => 0,
+        };
+        drop(statement);
+        for (i, step) in MIGRATIONS.iter().enumerate().skip(version) {
+            conn.execute(format!("BEGIN; {} PRAGMA user_version = {}; COMMIT;", step, i + 1))?;
+        }
+        Ok(Self { conn })
+    }
+
+    pub fn mark_read(&self, key: &str, at: DateTime<Utc>) -> sqlite::Result<()> {
+        let mut statement = self
+            .conn
+            .prepare("INSERT INTO read_items (key, read_at) VALUES (?, ?) ON CONFLICT(key) DO UPDATE SET read_at = excluded.read_at")?;
+        statement.bind((1, key))?;
+        statement.bind((2, at.timestamp()))?;
+        statement.next()?;
+        Ok(())
+    }
+
+    /// `mark_read` for many keys in one transaction.
+    pub fn mark_all_read(&self, keys: &[String], at: DateTime<Utc>) -> sqlite::Result<()> {
+        self.conn.execute("BEGIN")?;
+        for key in keys {
+            if let Err(e) = self.mark_read(key, at) {
+                let _ = self.conn.execute("ROLLBACK");
+                return Err(e);
+            }
+        }
+        self.conn.execute("COMMIT")
+    }
+
+    pub fn read_keys(&self) -> sqlite::Result<HashSet<String>> {
+        let mut statement = self.conn.prepare("SELECT key FROM read_items")?;
+        let mut keys = HashSet::new();
+        while let State::Row = statement.next()? {
+            keys.insert(statement.read::<String, _>(0)?);
+        }
+        Ok(keys)
+    }
+
+    /// Stars an item; starring it again just refreshes the saved copy.
+    pub fn add_bookmark(&self, bookmark: &Bookmark) -> sqlite::Result<()> {
+        let mut statement = self.conn.prepare(
+            "INSERT INTO bookmarks (feed_url, key, title, link, published, starred_at) VALUES (?, ?, ?, ?, ?, ?)
+             ON CONFLICT(feed_url, key) DO UPDATE SET title = excluded.title, link = excluded.link, published = excluded.published",
+        )?;
+        statement.bind((1, bookmark.feed_url.as_str()))?;
+        statement.bind((2, bookmark.key.as_str()))?;
+        statement.bind((3, bookmark.title.as_str()))?;
+        statement.bind((4, bookmark.link.as_str()))?;
+        statement.bind((5, bookmark.published.map(|at| at.timestamp())))?;
+        statement.bind((6, bookmark.starred_at.timestamp()))?;
+        statement.next()?;
+        Ok(())
+    }
+
+    pub fn remove_bookmark(&self, feed_url: &str, key: &str) -> sqlite::Result<()> {
+        let mut statement = self.conn.prepare("DELETE FROM bookmarks WHERE feed_url = ? AND key = ?")?;
+        statement.bind((1, feed_url))?;
+        statement.bind((2, key))?;
+        statement.next()?;
+        Ok(())
+    }
+
+    /// Every bookmark, most recently starred first.
+    pub fn bookmarks(&self) -> sqlite::Result<Vec<Bookmark>> {
+        let mut statement = self
+            .conn
+            .prepare("SELECT feed_url, key, title, link, published, starred_at FROM bookmarks ORDER BY starred_at DESC")?;
+        let timestamp = |secs: i64| DateTime::from_timestamp(secs, 0).unwrap_or_default();
+        let mut bookmarks = Vec::new();
+        while let State::Row = statement.next()? {
+            bookmarks.push(Bookmark {
+                feed_url: statement.read(0)?,
+                key: statement.read(1)?,
+                title: statement.read(2)?,
+                link: statement.read(3)?,
+                published: statement.read::<Option<i64>, _>(4)?.map(timestamp),
+                starred_at: timestamp(statement.read(5)?),
+            });
+        }
+        Ok(bookmarks)
+    }
+
+    /// Forgets items read more than `days` ago; returns how many were dropped.
+    pub fn prune(&self, days: u64, now: DateTime<Utc>) -> sqlite::Result<usize> {
+        let cutoff = now - Duration::days(days as i64);
+        let mut statement = self.conn.prepare("DELETE FROM read_items WHERE read_at < ?")?;
+        statement.bind((1, cutoff.timestamp()))?;
+        statement.next()?;
+        Ok(self.conn.change_count())
+    }
+}
+
+fn store_path() -> PathBuf {
+    env::var("RSS_TUI_DB")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(DEFAULT_STORE_PATH))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn temp_store() -> (TempDir, ReadStore) {
+        let dir = TempDir::new().unwrap();
+        let store = ReadStore::open(&dir.path().join("read.db")).unwrap();
+        (dir, store)
+    }
+
+    fn user_version(store: &ReadStore) -> i64 {
+        let mut statement = store.conn.prepare("PRAGMA user_version").unwrap();
+        statement.next().unwrap();
+        statement.read(0).unwrap()
+    }
+
+    #[test]
+    fn read_marks_round_trip() {
+        let (_dir, store) = temp_store();
+        let now = Utc::now();
+        store.mark_read("a", now).unwrap();
+        store.mark_read("a", now).unwrap();
+        store.mark_all_read(&["b".to_string(), "c".to_string()], now).unwrap();
+        let keys = store.read_keys().unwrap();
+        assert_eq!(keys, HashSet::from(["a", "b", "c"].map(String::from)));
+    }
+
+    #[test]
+    fn prune_drops_only_old_marks() {
+        let (_dir, store) = temp_store();
+        let now = Utc::now();
+        store.mark_read("old", now - Duration::days(40)).unwrap();
+        store.mark_read("recent", now - Duration::days(1)).unwrap();
+        assert_eq!(store.prune(30, now).unwrap(), 1);
+        assert_eq!(store.read_keys().unwrap(), HashSet::from(["recent".to_string()]));
+    }
+
+    fn bookmark(key: &str, title: &str, starred_at: DateTime<Utc>) -> Bookmark {
+        Bookmark {
+            feed_url: "https://example.com/feed.xml".to_string(),
+            key: key.to_string(),
+            title: title.to_string(),
+            link: format!("https://example.com/{}", key),
+            published: None,
+            starred_at,
+        }
+    }
+
+    #[test]
+    fn bookmarks_are_listed_newest_star_first() {
+        let (_dir, store) = temp_store();
+        let now = Utc::now();
+        store.add_bookmark(&bookmark("old", "Old", now - Duration::days(2))).unwrap();
+        store.add_bookmark(&bookmark("new", "New", now)).unwrap();
+        store.add_bookmark(&bookmark("mid", "Mid", now - Duration::days(1))).unwrap();
+        let keys: Vec<String> = store.bookmarks().unwrap().into_iter().map(|b| b.key).collect();
+        assert_eq!(keys, ["new", "mid", "old"]);
+    }
+
+    #[test]
+    fn starring_again_updates_the_saved_copy() {
+        let (_dir, store) = temp_store();
+        let first = Utc::now() - Duration::days(1);
+        store.add_bookmark(&bookmark("a", "Draft title", first)).unwrap();
+        store.add_bookmark(&bookmark("a", "Final title", Utc::now())).unwrap();
+        let bookmarks = store.bookmarks().unwrap();
+        assert_eq!(bookmarks.len(), 1);
+        assert_eq!(bookmarks[0].title, "Final title");
+        // Starring again refreshes the copy but keeps its place in the list
+        assert_eq!(bookmarks[0].starred_at.timestamp(), first.timestamp());
+    }
+
+    #[test]
+    fn removed_bookmarks_are_gone() {
+        let (_dir, store) = temp_store();
+        let now = Utc::now();
+        store.add_bookmark(&bookmark("a", "A", now)).unwrap();
+        store.add_bookmark(&bookmark("b", "B", now)).unwrap();
+        store.remove_bookmark("https://example.com/feed.xml", "a").unwrap();
+        store.remove_bookmark("https://example.com/feed.xml", "missing").unwrap();
+        let keys: Vec<String> = store.bookmarks().unwrap().into_iter().map(|b| b.key).collect();
+        assert_eq!(keys, ["b"]);
+    }
+
+    #[test]
+    fn reopening_keeps_data_and_finishes_migrations() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("read.db");
+        // A file from before bookmarks existed: only the first migration applied
+        let conn = Connection::open(&path).unwrap();
+        conn.execute(format!("{} PRAGMA user_version = 1;", MIGRATIONS[0])).unwrap();
+        conn.execute("INSERT INTO read_items (key, read_at) VALUES ('kept', 0)").unwrap();
+        drop(conn);
+
+        let store = ReadStore::open(&path).unwrap();
+        assert_eq!(user_version(&store), MIGRATIONS.len() as i64);
+        assert!(store.bookmarks().unwrap().is_empty());
+        drop(store);
+
+        let store = ReadStore::open(&path).unwrap();
+        assert_eq!(user_version(&store), MIGRATIONS.len() as i64);
+        assert_eq!(store.read_keys().unwrap(), HashSet::from(["kept".to_string()]));
+    }
+}
@@ -1,11 +1,81 @@
 use grammers_client::SignInError;
-use grammers_client::Client;
+use grammers_client::client::updates::UpdateStream;
+use grammers_client::{Client, InvocationError, Update, UpdatesConfiguration};
 use grammers_session::storages::SqliteSession;
+use grammers_session::updates::UpdatesLike;
 use grammers_mtsender::SenderPool;
+use chrono::{DateTime, Utc};
+use directories::ProjectDirs;
+use serde::Serialize;
 use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
 use std::io::{self, Write};
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+
+// Telegram's main production DC, used for the pre-flight connectivity check
+const PREFLIGHT_ADDR: &str = "149.154.167.51:443";
+const PREFLIGHT_TIMEOUT: Duration = Duration::from_secs(3);
+const SESSION_FILE: &str = "telegram.session";
+// Reconnect delays double from the first up to the cap
+const RECONNECT_FIRST_DELAY: Duration = Duration::from_secs(1);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(60);
+// Probes before a dropped connection stops the monitor and counts against its restarts (about 5 minutes)
+const RECONNECT_ATTEMPTS: u32 = 10;
+// A monitor run that lasted this long was healthy, so the restart budget starts over after it
+const HEALTHY_RUN: Duration = Duration::from_secs(10 * 60);
+
+/// Quick TCP probe so startup doesn't hang on a login that can't succeed.
+pub async fn is_reachable() -> bool {
+    matches!(
+        tokio::time::timeout(PREFLIGHT_TIMEOUT, TcpStream::connect(PREFLIGHT_ADDR)).await,
+        Ok(Ok(_))
+    )
+}
+
+/// `--session <path>`, then `$TG_SESSION_PATH`, then the per-user data directory
+/// (e.g. `~/.local/share/rss-tui/telegram.session`), so the login survives a change of directory.
+pub fn session_path() -> PathBuf {
+    crate::config::arg_value("--session")
+        .or_else(|| env::var("TG_SESSION_PATH").ok())
+        .map(PathBuf::from)
+        .or_else(|| ProjectDirs::from("", "", "rss-tui").map(|dirs| dirs.data_dir().join(SESSION_FILE)))
+        .unwrap_or_else(|| PathBuf::from(SESSION_FILE))
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TelegramMessage {
+    pub sender: String,
+    pub chat_id: i64,
+    pub text: String,
+    pub timestamp: DateTime<Utc>,
+    pub msg_id: i32,
+}
+
+/// Sent by `monitor` when Telegram stops accepting the session. The TUI owns the terminal, so it
+/// runs the login prompts and reports back on `done` before monitoring resumes.
+pub struct ReauthRequest {
+    pub client: Client,
+    pub done: oneshot::Sender<bool>,
+}
+
+/// Sent by `monitor` whenever the connection changes, for the indicator in the Telegram blocks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionState {
+    Connected,
+    /// Lost the connection (`error` says why) and waiting to try again
+    Reconnecting { attempt: u32, error: String },
+    /// `monitor` stopped with `error`; the supervisor starts it again after a pause
+    Restarting { restart: u32, max: u32, error: String },
+    /// Monitoring stopped for good after running out of restarts
+    Failed(String),
+}
 
 pub struct TelegramMonitor {
     pub last_seen: Arc<Mutex<HashMap<i64, i32>>>,
@@ -18,8 +88,18 @@ impl TelegramMonitor {
         }
     }
 
-    pub async fn create_client(&self, api_id: i32) -> anyhow::Result<Client> {
-        let session = Arc::new(SqliteSession::open("telegram.session")?);
+    /// Returns the client together with the raw update feed that `monitor` consumes.
+    /// The connection runs on `tasks` until the client is disconnected.
+    pub async fn create_client(
+        &self,
+        api_id: i32,
+        tasks: &TaskTracker,
+    ) -> anyhow::Result<(Client, mpsc::UnboundedReceiver<UpdatesLike>)> {
+        let path = session_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let session = Arc::new(SqliteSession::open(&path)?);
         let pool = SenderPool::new(Arc::clone(&session), api_id);
         let client = Client::new(&pool);
 
@@ -27,100 +107,245 @@ impl TelegramMonitor {
         let runner = pool.runner;
 
         // 2. You MUST move the runner into the spawned task
-        tokio::spawn(async move {
-            // We handle the result here so the block returns ()
-            let _ = runner.run().await;
-        }); 
+        // It returns once a client calls `disconnect`, which also lets the session close cleanly
+        tasks.spawn(runner.run());
 
-        Ok(client)
+        Ok((client, pool.updates))
     }
 
+    /// Signs in with `TG_BOT_TOKEN` when it's set, otherwise walks through the interactive
+    /// phone/code/2FA prompts on stdin.
     pub async fn ensure_authorized(&self, client: &Client, api_hash: &str) -> anyhow::Result<()> {
-        if !client.is_authorized().await? {
-            println!("--- Telegram Login Required ---");
-            print!("Enter phone (e.g. +123456789): ");
-            io::stdout().flush()?;
-            let mut phone = String::new();
-            io::stdin().read_line(&mut phone)?;
-            let phone = phone.trim();
-
-            // 1. Request the login code
-            let token = client.request_login_code(phone, api_hash).await?;
-            
-            print!("Enter the code sent to your Telegram: ");
-            io::stdout().flush()?;
-            let mut code = String::new();
-            io::stdin().read_line(&mut code)?;
-            let code = code.trim();
-
-            // 2. Attempt sign in
-            let login_result = client.sign_in(&token, code).await;
-            
-            match login_result {
-                Ok(user) => {
-                    println!("Signed in as {}!", user.first_name().unwrap_or("User"));
-                }
-                Err(SignInError::PasswordRequired(password_token)) => {
-                    // 3. Handle 2FA (Two-Factor Authentication)
-                    println!("2FA Required. Hint: {}", password_token.hint().unwrap_or("None"));
-                    print!("Enter 2FA password: ");
-                    io::stdout().flush()?;
-                    let mut password = String::new();
-                    io::stdin().read_line(&mut password)?;
-                    
-                    client.check_password(password_token, password.trim()).await?;
-                    println!("✅ 2FA Login successful!");
+        if client.is_authorized().await? {
+            return Ok(());
+        }
+        if let Ok(token) = env::var("TG_BOT_TOKEN") && !token.trim().is_empty() {
+            return match client.bot_sign_in(token.trim(), api_hash).await {
+                Ok(bot) => {
+                    println!("Signed in as bot {}!", bot.first_name().unwrap_or("Bot"));
+                    Ok(())
                 }
-                Err(e) => return Err(anyhow::anyhow!("Login failed: {}", e)),
+                Err(e) if e.is("ACCESS_TOKEN_*") => Err(anyhow::anyhow!(
+                    "Bot login failed: TG_BOT_TOKEN was rejected ({}); get a fresh token from @BotFather",
+                    e
+                )),
+                Err(e) => Err(anyhow::anyhow!("Bot login failed: {}", e)),
             };
-            
         }
+        println!("--- Telegram Login Required ---");
+        print!("Enter phone (e.g. +123456789): ");
+        io::stdout().flush()?;
+        let mut phone = String::new();
+        io::stdin().read_line(&mut phone)?;
+        let phone = phone.trim();
+
+        // 1. Request the login code
+        let token = client.request_login_code(phone, api_hash).await?;
+        
+        print!("Enter the code sent to your Telegram: ");
+        io::stdout().flush()?;
+        let mut code = String::new();
+        io::stdin().read_line(&mut code)?;
+        let code = code.trim();
+
+        // 2. Attempt sign in
+        let login_result = client.sign_in(&token, code).await;
+        
+        match login_result {
+            Ok(user) => {
+                println!("Signed in as {}!", user.first_name().unwrap_or("User"));
+            }
+            Err(SignInError::PasswordRequired(password_token)) => {
+                // 3. Handle 2FA (Two-Factor Authentication)
+                println!("2FA Required. Hint: {}", password_token.hint().unwrap_or("None"));
+                print!("Enter 2FA password: ");
+                io::stdout().flush()?;
+                let mut password = String::new();
+                io::stdin().read_line(&mut password)?;
+                
+                client.check_password(password_token, password.trim()).await?;
+                println!("✅ 2FA Login successful!");
+            }
+            Err(e) => return Err(anyhow::anyhow!("Login failed: {}", e)),
+        };
         Ok(())
     }
 
-    pub async fn monitor(
-        &self, 
-        client: Client, 
-        target_chat_ids: Vec<i64>, 
-        ui_tx: mpsc::UnboundedSender<(String, String)> 
-    ) -> anyhow::Result<()> {
+    /// Asks for confirmation, then signs out with Telegram and deletes the session file.
+    pub async fn logout(&self, api_id: i32) -> anyhow::Result<()> {
+        let path = session_path();
+        if !path.exists() {
+            println!("No Telegram session at {}; nothing to sign out of.", path.display());
+            return Ok(());
+        }
+        print!("Sign out of Telegram and delete {}? [y/N]: ", path.display());
+        io::stdout().flush()?;
+        let mut answer = String::new();
+        io::stdin().read_line(&mut answer)?;
+        if !answer.trim().eq_ignore_ascii_case("y") {
+            println!("Kept the session.");
+            return Ok(());
+        }
+
+        let tasks = TaskTracker::new();
+        let (client, _updates) = self.create_client(api_id, &tasks).await?;
+        // A session Telegram already forgot has nothing to sign out of, so just delete it
+        if client.is_authorized().await? {
+            client.sign_out().await?;
+        }
+        client.disconnect();
+        drop(client);
+        // Let the connection let go of the session file before deleting it
+        tasks.close();
+        tasks.wait().await;
+        fs::remove_file(&path)?;
+        println!("Signed out; {} deleted.", path.display());
+        Ok(())
+    }
+
+    /// Keeps `monitor` running: each time it stops, the error is reported on `status_tx` and
+    /// it's restarted on the same client and update stream after a backoff, up to `max_restarts`
+    /// times in a row; a run that stays up for `HEALTHY_RUN` resets the count. `last_seen` lives
+    /// on `self`, so a restart doesn't resend what the UI already has.
+    /// Cancelling `shutdown` saves the update state and disconnects instead.
+    #[allow(clippy::too_many_arguments)]
+    #[tracing::instrument(name = "telegram", skip_all, fields(chats = target_chat_ids.len()))]
+    pub async fn supervise(
+        &self,
+        client: Client,
+        updates: mpsc::UnboundedReceiver<UpdatesLike>,
+        target_chat_ids: Vec<i64>,
+        ui_tx: mpsc::UnboundedSender<TelegramMessage>,
+        reauth_tx: mpsc::UnboundedSender<ReauthRequest>,
+        status_tx: mpsc::UnboundedSender<ConnectionState>,
+        max_restarts: u32,
+        shutdown: CancellationToken,
+    ) {
+        // Telegram pushes new messages to us, so there's nothing to poll
+        let mut stream = client.stream_updates(updates, UpdatesConfiguration::default());
+        let mut delay = RECONNECT_FIRST_DELAY;
+        let mut restart = 0;
         loop {
-            // We re-fetch the dialog list each iteration to catch new messages
-            let mut dialogs = client.iter_dialogs();
+            let started = Instant::now();
+            let result = tokio::select! {
+                result = self.monitor(&client, &mut stream, &target_chat_ids, &ui_tx, &reauth_tx, &status_tx) => result,
+                _ = shutdown.cancelled() => break,
+            };
+            let Err(e) = result else { break };
+            // Drops spread over a long session shouldn't add up to giving up
+            if started.elapsed() >= HEALTHY_RUN {
+                restart = 0;
+                delay = RECONNECT_FIRST_DELAY;
+            }
+            restart += 1;
+            tracing::debug!(restart, error = %e, "monitor stopped");
+            if restart > max_restarts {
+                let _ = status_tx.send(ConnectionState::Failed(format!(
+                    "Telegram monitor gave up after {} restarts: {}", max_restarts, e
+                )));
+                break;
+            }
+            let _ = status_tx.send(ConnectionState::Restarting { restart, max: max_restarts, error: e.to_string() });
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = shutdown.cancelled() => break,
+            }
+            delay = (delay * 2).min(RECONNECT_MAX_DELAY);
+        }
+        stream.sync_update_state();
+        client.disconnect();
+    }
 
-            while let Some(dialog) = dialogs.next().await? {
-                let peer = dialog.peer();
-                let chat_id = peer.id().bot_api_dialog_id();
+    async fn monitor(
+        &self,
+        client: &Client,
+        stream: &mut UpdateStream,
+        target_chat_ids: &[i64],
+        ui_tx: &mpsc::UnboundedSender<TelegramMessage>,
+        reauth_tx: &mpsc::UnboundedSender<ReauthRequest>,
+        status_tx: &mpsc::UnboundedSender<ConnectionState>,
+    ) -> anyhow::Result<()> {
+        let _ = status_tx.send(ConnectionState::Connected);
+        tracing::debug!("monitoring");
 
-                if !target_chat_ids.contains(&chat_id) {
+        loop {
+            let update = match stream.next().await {
+                Ok(update) => update,
+                // 401: the session was revoked or expired, so ask the UI to log in again
+                Err(InvocationError::Rpc(e)) if e.code == 401 => {
+                    tracing::debug!(error = %e, "session expired, asking for a login");
+                    let (done, logged_in) = oneshot::channel();
+                    reauth_tx.send(ReauthRequest { client: client.clone(), done })?;
+                    if logged_in.await != Ok(true) {
+                        return Err(anyhow::anyhow!("Telegram session expired: {}", e));
+                    }
+                    continue;
+                }
+                Err(e) => {
+                    self.reconnect(client, e, status_tx).await?;
                     continue;
                 }
+            };
+            let Update::NewMessage(msg) = update else { continue };
 
-                if let Some(msg) = dialog.last_message.as_ref() {
-                    let msg_id = msg.id();
+            let chat_id = msg.peer_id().bot_api_dialog_id();
+            if !target_chat_ids.contains(&chat_id) {
+                continue;
+            }
+            tracing::trace!(chat_id, msg_id = msg.id(), "message");
 
-                    // Deduplication logic using the Mutex-wrapped last_seen map
-                    {
-                        let mut last_seen = self.last_seen.lock().unwrap();
-                        if let Some(&prev_id) = last_seen.get(&chat_id) {
-                            if msg_id <= prev_id { continue; }
-                        }
-                        last_seen.insert(chat_id, msg_id);
-                    }
+            // Updates can be redelivered after a reconnect; last_seen drops the repeats.
+            // The map is only ids, so one left behind by a panicking holder is still usable.
+            {
+                let mut last_seen = self.last_seen.lock().unwrap_or_else(|e| e.into_inner());
+                if last_seen.get(&chat_id).is_some_and(|&prev| msg.id() <= prev) {
+                    continue;
+                }
+                last_seen.insert(chat_id, msg.id());
+            }
 
-                    let sender_name = peer.name()
-                        .map(|s| s.to_owned())
-                        .unwrap_or_else(|| "Unknown".to_string());
+            let sender_name = msg.peer().ok()
+                .and_then(|peer| peer.name())
+                .map(|s| s.to_owned())
+                .unwrap_or_else(|| "Unknown".to_string());
 
-                    let clean_text = msg.text().replace('\n', " ");
-                    
-                    // Send to the channel which main.rs is listening to
-                    let _ = ui_tx.send((sender_name, clean_text));
+            // Send to the channel which main.rs is listening to
+            let _ = ui_tx.send(TelegramMessage {
+                sender: sender_name,
+                chat_id,
+                text: msg.text().replace('\n', " "),
+                timestamp: msg.date(),
+                msg_id: msg.id(),
+            });
+        }
+    }
+
+    /// Backs off until Telegram answers a request again, for up to `RECONNECT_ATTEMPTS` tries,
+    /// then fails so `supervise` counts it as a restart. The sender pool redials on its own once
+    /// asked to send something, so a cheap `get_me` is both the probe and the reconnect.
+    async fn reconnect(
+        &self,
+        client: &Client,
+        error: InvocationError,
+        status_tx: &mpsc::UnboundedSender<ConnectionState>,
+    ) -> anyhow::Result<()> {
+        let mut error = error.to_string();
+        let mut delay = RECONNECT_FIRST_DELAY;
+        for attempt in 1..=RECONNECT_ATTEMPTS {
+            tracing::debug!(attempt, %error, "reconnecting");
+            let _ = status_tx.send(ConnectionState::Reconnecting { attempt, error: error.clone() });
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(RECONNECT_MAX_DELAY);
+            match client.get_me().await {
+                // Any RPC answer, even a refusal, means we're talking to Telegram again;
+                // a 401 will come back through the update stream and trigger a login
+                Ok(_) | Err(InvocationError::Rpc(_)) => {
+                    let _ = status_tx.send(ConnectionState::Connected);
+                    return Ok(());
                 }
+                Err(e) => error = e.to_string(),
             }
-
-            // Wait for 2 seconds before checking for new "Latest Messages" again
-            tokio::time::sleep(std::time::Duration::from_secs(2)).await;
         }
+        Err(anyhow::anyhow!("no connection after {} attempts: {}", RECONNECT_ATTEMPTS, error))
     }
-}
\ No newline at end of file
+}
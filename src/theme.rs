@@ -0,0 +1,365 @@
+use ratatui::style::Color;
+use serde::{Deserialize, Deserializer};
+use std::borrow::Cow;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Themes selectable by name, in the order [C] cycles through them.
+pub const BUILT_IN: [(&str, Theme); 3] = [
+    ("matrix", Theme::MATRIX),
+    ("light", Theme::LIGHT),
+    ("high-contrast", Theme::HIGH_CONTRAST),
+];
+
+/// How many colours the terminal can show; RGB values are mapped down to fit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorLevel {
+    #[default]
+    TrueColor,
+    Ansi256,
+    Ansi16,
+}
+
+impl ColorLevel {
+    /// `--colors truecolor|256|16` if given, else a guess from `COLORTERM` and `TERM`.
+    pub fn detect() -> anyhow::Result<ColorLevel> {
+        let forced = crate::config::arg_value("--colors");
+        Self::choose(forced.as_deref(), &env::var("COLORTERM").unwrap_or_default(), &env::var("TERM").unwrap_or_default())
+    }
+
+    fn choose(forced: Option<&str>, colorterm: &str, term: &str) -> anyhow::Result<ColorLevel> {
+        if let Some(forced) = forced {
+            return match forced {
+                "truecolor" | "24bit" => Ok(ColorLevel::TrueColor),
+                "256" => Ok(ColorLevel::Ansi256),
+                "16" => Ok(ColorLevel::Ansi16),
+                other => Err(anyhow::anyhow!("--colors expects truecolor, 256 or 16, got {:?}", other)),
+            };
+        }
+        Ok(if colorterm == "truecolor" || colorterm == "24bit" {
+            ColorLevel::TrueColor
+        } else if term.contains("256color") {
+            ColorLevel::Ansi256
+        } else {
+            ColorLevel::Ansi16
+        })
+    }
+
+    /// The closest colour this level can show; anything but RGB passes through unchanged.
+    pub fn adapt(self, color: Color) -> Color {
+        let Color::Rgb(r, g, b) = color else { return color };
+        match self {
+            ColorLevel::TrueColor => color,
+            ColorLevel::Ansi256 => Color::Indexed(nearest_256(r, g, b)),
+            ColorLevel::Ansi16 => nearest_16(r, g, b),
+        }
+    }
+}
+
+/// The UI colours, plus the palette feeds without a colour of their own cycle through.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    #[serde(deserialize_with = "de_color")]
+    pub background: Color,
+    /// Block borders, separators and the selected palette row
+    #[serde(deserialize_with = "de_color")]
+    pub border: Color,
+    /// Feeds without a colour of their own take these in turn, by position in the config.
+    /// Older theme files set a single `accent` colour, read as a palette of one.
+    #[serde(alias = "accent", deserialize_with = "de_palette")]
+    pub feed_colors: Cow<'static, [Color]>,
+    /// Search matches and warnings
+    #[serde(deserialize_with = "de_color")]
+    pub highlight: Color,
+    #[serde(deserialize_with = "de_color")]
+    pub telegram: Color,
+    #[serde(deserialize_with = "de_color")]
+    pub text: Color,
+    /// Descriptions, dates and read items
+    #[serde(deserialize_with = "de_color")]
+    pub muted: Color,
+    /// Overlay titles and the footer badge
+    #[serde(deserialize_with = "de_color")]
+    pub chrome: Color,
+    #[serde(deserialize_with = "de_color")]
+    pub error: Color,
+    /// Applied to feed colours at draw time; `with_level` has already applied it to the fields above
+    #[serde(skip)]
+    pub level: ColorLevel,
+}
+
+impl Theme {
+    pub const MATRIX: Theme = Theme {
+        background: Color::Rgb(15, 15, 20),
+        border: Color::Rgb(50, 50, 60),
+        feed_colors: Cow::Borrowed(&[Color::Rgb(0, 235, 65), Color::Rgb(255, 0, 255), Color::Rgb(0, 255, 255), Color::Rgb(255, 170, 50)]),
+        highlight: Color::Rgb(255, 170, 50),
+        telegram: Color::Rgb(0, 136, 204),
+        text: Color::White,
+        muted: Color::Rgb(120, 120, 130),
+        chrome: Color::Rgb(160, 160, 170),
+        error: Color::Rgb(255, 85, 85),
+        level: ColorLevel::TrueColor,
+    };
+
+    pub const LIGHT: Theme = Theme {
+        background: Color::Rgb(250, 250, 245),
+        border: Color::Rgb(200, 200, 205),
+        feed_colors: Cow::Borrowed(&[Color::Rgb(0, 135, 60), Color::Rgb(170, 0, 140), Color::Rgb(0, 120, 150), Color::Rgb(190, 110, 0)]),
+        highlight: Color::Rgb(215, 130, 0),
+        telegram: Color::Rgb(0, 110, 180),
+        text: Color::Rgb(25, 25, 30),
+        muted: Color::Rgb(110, 110, 120),
+        chrome: Color::Rgb(70, 70, 80),
+        error: Color::Rgb(200, 30, 30),
+        level: ColorLevel::TrueColor,
+    };
+
+    /// Pure black and white with saturated accents, for low-quality displays and low vision.
+    pub const HIGH_CONTRAST: Theme = Theme {
+        background: Color::Rgb(0, 0, 0),
+        border: Color::Rgb(128, 128, 128),
+        feed_colors: Cow::Borrowed(&[Color::Rgb(0, 255, 0), Color::Rgb(255, 0, 255), Color::Rgb(0, 255, 255), Color::Rgb(255, 255, 0)]),
+        highlight: Color::Rgb(255, 255, 0),
+        telegram: Color::Rgb(0, 200, 255),
+        text: Color::Rgb(255, 255, 255),
+        muted: Color::Rgb(210, 210, 210),
+        chrome: Color::Rgb(255, 255, 255),
+        error: Color::Rgb(255, 70, 70),
+        level: ColorLevel::TrueColor,
+    };
+
+    /// A built-in theme by name (see `BUILT_IN`), or else a TOML file of hex colours.
+    /// Keys missing from the file keep their "matrix" value.
+    pub fn load(name: &str) -> anyhow::Result<Theme> {
+        if let Some((_, theme)) = BUILT_IN.iter().find(|(builtin, _)| *builtin == name) {
+            return Ok(theme.clone());
+        }
+        let path = Path::new(name);
+        let text = fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Theme {:?} is neither built in nor a readable file: {}", path, e))?;
+        toml::from_str(&text).map_err(|e| anyhow::anyhow!("Invalid theme {}: {}", path.display(), e))
+    }
+
+    /// This theme with every colour mapped down to what `level` can show.
+    pub fn with_level(self, level: ColorLevel) -> Theme {
+        Theme {
+            background: level.adapt(self.background),
+            border: level.adapt(self.border),
+            feed_colors: self.feed_colors.iter().map(|&color| level.adapt(color)).collect(),
+            highlight: level.adapt(self.highlight),
+            telegram: level.adapt(self.telegram),
+            text: level.adapt(self.text),
+            muted: level.adapt(self.muted),
+            chrome: level.adapt(self.chrome),
+            error: level.adapt(self.error),
+            level,
+        }
+    }
+
+    /// The palette colour for the feed at `index` in the config.
+    pub fn feed_color(&self, index: usize) -> Color {
+        self.feed_colors[index % self.feed_colors.len()]
+    }
+
+    /// A colour from outside the theme (a feed's own), fitted to the terminal.
+    pub fn adapt(&self, color: Color) -> Color {
+        self.level.adapt(color)
+    }
+
+    /// The built-in theme after `name`; a theme file is followed by the first built-in.
+    pub fn next_built_in(name: &str) -> (&'static str, Theme) {
+        let next = BUILT_IN.iter().position(|(builtin, _)| *builtin == name).map_or(0, |i| (i + 1) % BUILT_IN.len());
+        BUILT_IN[next].clone()
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::MATRIX
+    }
+}
+
+/// `#RRGGBB` or `RRGGBB` as an RGB colour, or the CSS shorthand `#RGB` (`#f80` is `#ff8800`).
+pub fn parse_hex_color(raw: &str) -> anyhow::Result<Color> {
+    let hex = raw.strip_prefix('#').unwrap_or(raw);
+    if let Some(bad) = hex.chars().find(|c| !c.is_ascii_hexdigit()) {
+        anyhow::bail!("{:?} is not a hex colour: {:?} is not a hex digit", raw, bad);
+    }
+    let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).expect("checked hex digits");
+    match hex.len() {
+        3 => Ok(Color::Rgb(digit(0) * 17, digit(1) * 17, digit(2) * 17)),
+        6 => Ok(Color::Rgb(digit(0) * 16 + digit(1), digit(2) * 16 + digit(3), digit(4) * 16 + digit(5))),
+        len => anyhow::bail!("{:?} is not a hex colour: expected 6 digits (RRGGBB) or 3 (RGB), got {}", raw, len),
+    }
+}
+
+/// A hex colour (see `parse_hex_color`) or one of the terminal's named colours ("cyan", "lightred").
+pub fn parse_color(raw: &str) -> anyhow::Result<Color> {
+    let raw = raw.trim();
+    if raw.starts_with('#') || (raw.len() == 6 && raw.chars().all(|c| c.is_ascii_hexdigit())) {
+        return parse_hex_color(raw);
+    }
+    raw.parse::<Color>()
+        .map_err(|_| anyhow::anyhow!("{:?} is neither a hex colour (#RRGGBB or #RGB) nor a colour name", raw))
+}
+
+pub(crate) fn de_color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    let raw = String::deserialize(deserializer)?;
+    parse_color(&raw).map_err(serde::de::Error::custom)
+}
+
+/// A list of colours, or the single colour the old `accent` key held; empty lists are rejected.
+fn de_palette<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Cow<'static, [Color]>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        One(String),
+        Many(Vec<String>),
+    }
+    let raw = match Raw::deserialize(deserializer)? {
+        Raw::One(color) => vec![color],
+        Raw::Many(colors) => colors,
+    };
+    if raw.is_empty() {
+        return Err(serde::de::Error::custom("feed_colors needs at least one colour"));
+    }
+    let colors = raw.iter().map(|c| parse_color(c)).collect::<anyhow::Result<Vec<_>>>().map_err(serde::de::Error::custom)?;
+    Ok(Cow::Owned(colors))
+}
+
+/// xterm's 6x6x6 cube (16-231) or grey ramp (232-255), whichever lands closer.
+fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+    const STEPS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let step = |v: u8| STEPS.iter().enumerate().min_by_key(|(_, s)| s.abs_diff(v)).map_or(0, |(i, _)| i as u8);
+    let (ri, gi, bi) = (step(r), step(g), step(b));
+    let cube = 16 + 36 * ri + 6 * gi + bi;
+    let cube_rgb = (STEPS[ri as usize], STEPS[gi as usize], STEPS[bi as usize]);
+
+    let average = (r as u16 + g as u16 + b as u16) / 3;
+    let grey_step = (average.saturating_sub(3) / 10).min(23) as u8;
+    let grey = 8 + 10 * grey_step;
+    if distance((r, g, b), (grey, grey, grey)) < distance((r, g, b), cube_rgb) {
+        232 + grey_step
+    } else {
+        cube
+    }
+}
+
+fn nearest_16(r: u8, g: u8, b: u8) -> Color {
+    const PALETTE: [(Color, (u8, u8, u8)); 16] = [
+        (Color::Black, (0, 0, 0)),
+        (Color::Red, (128, 0, 0)),
+        (Color::Green, (0, 128, 0)),
+        (Color::Yellow, (128, 128, 0)),
+        (Color::Blue, (0, 0, 128)),
+        (Color::Magenta, (128, 0, 128)),
+        (Color::Cyan, (0, 128, 128)),
+        (Color::Gray, (192, 192, 192)),
+        (Color::DarkGray, (128, 128, 128)),
+        (Color::LightRed, (255, 0, 0)),
+        (Color::LightGreen, (0, 255, 0)),
+        (Color::LightYellow, (255, 255, 0)),
+        (Color::LightBlue, (0, 0, 255)),
+        (Color::LightMagenta, (255, 0, 255)),
+        (Color::LightCyan, (0, 255, 255)),
+        (Color::White, (255, 255, 255)),
+    ];
+    PALETTE.iter().min_by_key(|(_, rgb)| distance((r, g, b), *rgb)).map_or(Color::Reset, |(color, _)| *color)
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hex_colours() {
+        assert_eq!(parse_hex_color("#00eb41").unwrap(), Color::Rgb(0, 235, 65));
+        assert_eq!(parse_hex_color("FFaa32").unwrap(), Color::Rgb(255, 170, 50), "the '#' is optional");
+        assert_eq!(parse_hex_color("#f80").unwrap(), Color::Rgb(255, 136, 0));
+        assert_eq!(parse_color("#f80").unwrap(), Color::Rgb(255, 136, 0));
+    }
+
+    #[test]
+    fn rejects_junk_colours() {
+        for raw in ["", "#", "#12345", "#1234567", "#gg0000", "#ff 000", "#ffé000"] {
+            assert!(parse_hex_color(raw).is_err(), "{:?}", raw);
+        }
+        assert!(parse_color("not-a-colour").is_err());
+        assert_eq!(parse_color("lightred").unwrap(), Color::LightRed);
+    }
+
+    #[test]
+    fn rgb_maps_to_the_nearest_256_colour() {
+        assert_eq!(nearest_256(0, 0, 0), 16);
+        assert_eq!(nearest_256(255, 255, 255), 231);
+        assert_eq!(nearest_256(255, 0, 0), 196);
+        assert_eq!(nearest_256(0, 135, 175), 16 + 6 * 2 + 3);
+        // Greys land on the finer grey ramp
+        assert_eq!(nearest_256(128, 128, 128), 244);
+        assert_eq!(nearest_256(15, 15, 20), 233);
+    }
+
+    #[test]
+    fn rgb_maps_to_the_nearest_16_colour() {
+        assert_eq!(nearest_16(250, 10, 10), Color::LightRed);
+        assert_eq!(nearest_16(120, 5, 5), Color::Red);
+        assert_eq!(nearest_16(0, 136, 204), Color::Cyan);
+        assert_eq!(nearest_16(200, 200, 200), Color::Gray);
+        assert_eq!(nearest_16(15, 15, 20), Color::Black);
+    }
+
+    #[test]
+    fn level_comes_from_the_flag_then_the_environment() {
+        assert_eq!(ColorLevel::choose(None, "truecolor", "xterm").unwrap(), ColorLevel::TrueColor);
+        assert_eq!(ColorLevel::choose(None, "", "xterm-256color").unwrap(), ColorLevel::Ansi256);
+        assert_eq!(ColorLevel::choose(None, "", "linux").unwrap(), ColorLevel::Ansi16);
+        assert_eq!(ColorLevel::choose(Some("16"), "truecolor", "xterm-256color").unwrap(), ColorLevel::Ansi16);
+        assert_eq!(ColorLevel::choose(Some("24bit"), "", "linux").unwrap(), ColorLevel::TrueColor);
+        assert!(ColorLevel::choose(Some("8"), "", "").is_err());
+    }
+
+    #[test]
+    fn adapt_follows_the_level() {
+        let orange = Color::Rgb(255, 170, 50);
+        assert_eq!(ColorLevel::TrueColor.adapt(orange), orange);
+        assert_eq!(ColorLevel::Ansi256.adapt(orange), Color::Indexed(nearest_256(255, 170, 50)));
+        assert_eq!(ColorLevel::Ansi16.adapt(orange), nearest_16(255, 170, 50));
+        // Named and indexed colours are already within reach
+        assert_eq!(ColorLevel::Ansi16.adapt(Color::Cyan), Color::Cyan);
+        assert_eq!(ColorLevel::Ansi16.adapt(Color::Indexed(99)), Color::Indexed(99));
+
+        let theme = Theme::MATRIX.with_level(ColorLevel::Ansi16);
+        assert_eq!(theme.text, Color::White);
+        assert_eq!(theme.feed_color(0), nearest_16(0, 235, 65));
+        assert_eq!(theme.adapt(Color::Rgb(255, 0, 255)), Color::LightMagenta);
+    }
+
+    #[test]
+    fn accent_reads_as_a_palette_of_one() {
+        let theme: Theme = toml::from_str(r##"accent = "#00eb41""##).unwrap();
+        assert_eq!(theme.feed_colors.as_ref(), [Color::Rgb(0, 235, 65)]);
+        assert_eq!(theme.feed_color(3), Color::Rgb(0, 235, 65));
+    }
+
+    #[test]
+    fn palettes_of_any_length_cycle() {
+        let theme: Theme = toml::from_str(r##"feed_colors = ["#010203", "cyan"]"##).unwrap();
+        let colors: Vec<Color> = (0..5).map(|i| theme.feed_color(i)).collect();
+        let (a, b) = (Color::Rgb(1, 2, 3), Color::Cyan);
+        assert_eq!(colors, [a, b, a, b, a]);
+    }
+
+    #[test]
+    fn empty_palette_is_rejected() {
+        let err = toml::from_str::<Theme>("feed_colors = []").unwrap_err();
+        assert!(err.to_string().contains("at least one colour"), "{}", err);
+    }
+}